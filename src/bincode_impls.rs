@@ -0,0 +1,82 @@
+// Copyright 2019 Octavian Oncescu
+
+use ::serde::de::DeserializeOwned;
+use ::serde::ser::Serialize;
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::hash::{BuildHasher, Hash};
+use crate::CowHashMap;
+
+impl<'a, K, V, S> CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Serializes the map into the `bincode` binary format.
+    /// 
+    /// Borrowed and owned entries are serialized identically. This
+    /// function is only available with the `bincode` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let bytes = hm.to_bincode().unwrap();
+    /// let decoded: CowHashMap<str, [u8]> = CowHashMap::from_bincode(&bytes).unwrap();
+    /// 
+    /// assert_eq!(decoded, hm);
+    /// ```
+    #[inline]
+    pub fn to_bincode(&self) -> Result<Vec<u8>, bincode::Error> 
+        where K: Serialize,
+              V: Serialize,
+    {
+        bincode::serialize(self)
+    }
+
+    /// Deserializes a map from the `bincode` binary format.
+    /// 
+    /// All of the resulting entries are in owned form. This function
+    /// is only available with the `bincode` feature.
+    #[inline]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, bincode::Error> 
+        where S: Default,
+              <K as ToOwned>::Owned: DeserializeOwned,
+              <V as ToOwned>::Owned: DeserializeOwned,
+    {
+        bincode::deserialize(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Form, CowHashMap};
+
+    #[test]
+    fn bincode_round_trip() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let bytes = hm.to_bincode().unwrap();
+        let decoded: CowHashMap<str, [u8]> = CowHashMap::from_bincode(&bytes).unwrap();
+
+        assert_eq!(decoded, hm);
+        assert_eq!(decoded.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(decoded.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn borrowed_and_owned_encode_identically() {
+        let mut borrowed: CowHashMap<str, [u8]> = CowHashMap::new();
+        borrowed.insert_borrowed("key", &[1, 2, 3]);
+
+        let mut owned: CowHashMap<str, [u8]> = CowHashMap::new();
+        owned.insert_owned("key".to_owned(), vec![1, 2, 3]);
+
+        assert_eq!(borrowed.to_bincode().unwrap(), owned.to_bincode().unwrap());
+    }
+}
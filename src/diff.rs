@@ -0,0 +1,53 @@
+// Copyright 2019 Octavian Oncescu
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// The difference between two `CowHashMap`s.
+///
+/// This is constructed from the `diff` method on `CowHashMap` and
+/// describes how to turn the map it was called on into the other
+/// map. The entries are stored in owned form so that the diff does
+/// not borrow from either map. The order of the entries in each
+/// list is unspecified.
+pub struct MapDiff<K, V>
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    /// Entries which are only present in the other map.
+    pub added: Vec<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)>,
+
+    /// Keys which are only present in the original map.
+    pub removed: Vec<<K as ToOwned>::Owned>,
+
+    /// Entries which are present in both maps but with different
+    /// values, along with the value of the other map.
+    pub changed: Vec<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)>,
+}
+
+impl<K, V> MapDiff<K, V>
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    /// Returns `true` if the diff contains no changes.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+impl<K, V> fmt::Debug for MapDiff<K, V>
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+          <K as ToOwned>::Owned: fmt::Debug,
+          <V as ToOwned>::Owned: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MapDiff")
+            .field("added", &self.added)
+            .field("removed", &self.removed)
+            .field("changed", &self.changed)
+            .finish()
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright 2019 Octavian Oncescu
+
+use hashbrown::hash_map::{self, DefaultHashBuilder};
+use alloc::borrow::{Cow, ToOwned};
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use crate::Form;
+
+/// A view into a single entry in a `CowHashMap`, which may either
+/// be vacant or occupied.
+///
+/// This is constructed from the `entry` method on `CowHashMap`.
+pub enum Entry<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    /// An occupied entry.
+    Occupied(OccupiedEntry<'b, 'a, K, V, S>),
+
+    /// A vacant entry.
+    Vacant(VacantEntry<'b, 'a, K, V, S>),
+}
+
+/// A view into an occupied entry in a `CowHashMap`.
+pub struct OccupiedEntry<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    pub(crate) inner: hash_map::OccupiedEntry<'b, Cow<'a, K>, Cow<'a, V>, S>
+}
+
+/// A view into a vacant entry in a `CowHashMap`.
+pub struct VacantEntry<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    pub(crate) inner: hash_map::VacantEntry<'b, Cow<'a, K>, Cow<'a, V>, S>
+}
+
+/// The error returned by `try_insert_owned` when the key is
+/// already present in the map.
+///
+/// Contains the occupied entry and the value that was not inserted.
+pub struct OccupiedError<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    /// The entry in the map that was already occupied.
+    pub entry: OccupiedEntry<'b, 'a, K, V, S>,
+
+    /// The value which was not inserted, because the entry was already occupied.
+    pub value: <V as ToOwned>::Owned,
+}
+
+impl<'b, 'a, K, V, S> fmt::Debug for OccupiedError<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned + fmt::Debug,
+          V: ToOwned + ?Sized + fmt::Debug,
+          <V as ToOwned>::Owned: fmt::Debug,
+          S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OccupiedError")
+            .field("key", &self.entry.key())
+            .field("old_value", &self.entry.get())
+            .field("new_value", &self.value)
+            .finish()
+    }
+}
+
+impl<'b, 'a, K, V, S> fmt::Display for OccupiedError<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned + fmt::Debug,
+          V: ToOwned + ?Sized + fmt::Debug,
+          <V as ToOwned>::Owned: fmt::Debug,
+          S: BuildHasher,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to insert {:?}, key {:?} already exists with value {:?}",
+            self.value,
+            self.entry.key(),
+            self.entry.get(),
+        )
+    }
+}
+
+impl<'b, 'a, K, V, S> Entry<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Ensures a value is in the entry by inserting the given owned
+    /// default if empty, and returns a mutable reference to the owned
+    /// form of the value in the entry.
+    ///
+    /// If the entry is occupied by a borrowed value, it will be cloned.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    ///
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.entry("key".to_owned()).or_insert_owned(vec![1, 2, 3]).push(4);
+    ///
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn or_insert_owned(self, default: <V as ToOwned>::Owned) -> &'b mut <V as ToOwned>::Owned {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert_owned(default),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the owned result of
+    /// the given function if empty, and returns a mutable reference to
+    /// the owned form of the value in the entry.
+    ///
+    /// The function is given a reference to the entry's key, and is only
+    /// called if the entry is vacant. If the entry is occupied by a
+    /// borrowed value, it will be cloned.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    ///
+    /// let mut hm: CowHashMap<str, usize> = CowHashMap::new();
+    /// hm.entry("key".to_owned()).or_insert_owned_with_key(|k| k.len());
+    ///
+    /// assert_eq!(hm.get(&"key").unwrap(), &3);
+    /// ```
+    #[inline]
+    pub fn or_insert_owned_with_key<F>(self, f: F) -> &'b mut <V as ToOwned>::Owned
+        where F: FnOnce(&K) -> <V as ToOwned>::Owned
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => {
+                let value = f(entry.key());
+                entry.insert_owned(value)
+            }
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the given borrowed
+    /// default if empty, and returns a reference to the value in the entry.
+    ///
+    /// This function does not alter the form of an occupied entry.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    ///
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.entry("key".to_owned()).or_insert_borrowed(&[1, 2, 3]);
+    ///
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn or_insert_borrowed(self, default: &'a V) -> &'b V {
+        match self {
+            Entry::Occupied(entry) => entry.into_ref(),
+            Entry::Vacant(entry) => entry.insert_borrowed(default),
+        }
+    }
+
+    /// Provides in-place mutable access to the owned form of an
+    /// occupied entry before any potential inserts into the map.
+    ///
+    /// If the entry is occupied by a borrowed value, it will be cloned
+    /// before being passed to the closure. Vacant entries are left untouched.
+    ///
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    ///
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    ///
+    /// hm.entry("key".to_owned())
+    ///     .and_modify(|v| v.push(4))
+    ///     .or_insert_owned(vec![]);
+    ///
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn and_modify<F>(self, f: F) -> Self
+        where F: FnOnce(&mut <V as ToOwned>::Owned)
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+
+    /// Returns a reference to this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+}
+
+impl<'b, 'a, K, V, S> OccupiedEntry<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Returns a reference to this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.inner.key().borrow()
+    }
+
+    /// Returns a reference to the value in the entry.
+    ///
+    /// This does not alter the form of the entry.
+    #[inline]
+    pub fn get(&self) -> &V {
+        self.inner.get().as_ref()
+    }
+
+    /// Returns a mutable reference to the owned form of the value
+    /// in the entry.
+    ///
+    /// If the value is borrowed, it will be cloned.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut <V as ToOwned>::Owned {
+        self.inner.get_mut().to_mut()
+    }
+
+    /// Converts the entry into a mutable reference to the owned form
+    /// of its value, with a lifetime bound to the map itself.
+    ///
+    /// If the value is borrowed, it will be cloned.
+    #[inline]
+    pub fn into_mut(self) -> &'b mut <V as ToOwned>::Owned {
+        self.inner.into_mut().to_mut()
+    }
+
+    /// Converts the entry into a reference to its value, with a
+    /// lifetime bound to the map itself.
+    ///
+    /// This does not alter the form of the entry.
+    #[inline]
+    pub fn into_ref(self) -> &'b V {
+        self.inner.into_mut()
+    }
+
+    /// Returns the form in which the value of the entry is stored.
+    #[inline]
+    pub fn form(&self) -> Form {
+        Form::of(self.inner.get())
+    }
+
+    /// Sets the value of the entry in owned form, and returns the
+    /// entry's old value. If the old value is borrowed, it will be
+    /// cloned and then returned.
+    #[inline]
+    pub fn insert_owned(&mut self, value: <V as ToOwned>::Owned) -> <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Owned(value)).into_owned()
+    }
+
+    /// Sets the value of the entry in borrowed form, and returns the
+    /// entry's old value. If the old value is borrowed, it will be
+    /// cloned and then returned.
+    #[inline]
+    pub fn insert_borrowed(&mut self, value: &'a V) -> <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Borrowed(value)).into_owned()
+    }
+
+    /// Takes the value out of the entry, and returns it in owned form.
+    #[inline]
+    pub fn remove(self) -> <V as ToOwned>::Owned {
+        self.inner.remove().into_owned()
+    }
+}
+
+impl<'b, 'a, K, V, S> VacantEntry<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Returns a reference to the key that would be used when
+    /// inserting a value through the `VacantEntry`.
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.inner.key().borrow()
+    }
+
+    /// Takes ownership of the key.
+    #[inline]
+    pub fn into_key(self) -> <K as ToOwned>::Owned {
+        self.inner.into_key().into_owned()
+    }
+
+    /// Sets the value of the entry in owned form, and returns a
+    /// mutable reference to it.
+    #[inline]
+    pub fn insert_owned(self, value: <V as ToOwned>::Owned) -> &'b mut <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Owned(value)).to_mut()
+    }
+
+    /// Sets the value of the entry in borrowed form, and returns a
+    /// reference to it.
+    #[inline]
+    pub fn insert_borrowed(self, value: &'a V) -> &'b V {
+        self.inner.insert(Cow::Borrowed(value))
+    }
+}
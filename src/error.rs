@@ -0,0 +1,40 @@
+// Copyright 2019 Octavian Oncescu
+
+use core::fmt;
+
+/// The error returned by `insert_owned_capped` when inserting a new
+/// key would grow the map beyond the given maximum length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CapacityExceeded {
+    /// The maximum length that the insertion would have exceeded.
+    pub max_len: usize,
+}
+
+impl fmt::Display for CapacityExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "map is already at its maximum length of {}", self.max_len)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CapacityExceeded {}
+
+/// The error returned by `insert_zip_borrowed` when the given key
+/// and value slices have different lengths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// The length of the key slice.
+    pub keys: usize,
+
+    /// The length of the value slice.
+    pub values: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "key and value slices have different lengths ({} and {})", self.keys, self.values)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
@@ -0,0 +1,58 @@
+// Copyright 2019 Octavian Oncescu
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+/// Types which can estimate the number of bytes they
+/// own on the heap.
+///
+/// This is used by `CowHashMap::memory_size_hint` to account
+/// for the data held by owned entries. The estimate excludes
+/// the inline size of the value itself.
+pub trait HeapSize {
+    /// Returns the number of heap allocated bytes owned by the value.
+    fn heap_size(&self) -> usize;
+}
+
+macro_rules! impl_heap_size_zero {
+    ($($t:ty),*) => {
+        $(
+            impl HeapSize for $t {
+                #[inline]
+                fn heap_size(&self) -> usize {
+                    0
+                }
+            }
+        )*
+    };
+}
+
+impl_heap_size_zero!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char);
+
+impl HeapSize for String {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity()
+    }
+}
+
+impl<T: HeapSize> HeapSize for Vec<T> {
+    #[inline]
+    fn heap_size(&self) -> usize {
+        self.capacity() * size_of::<T>() + self.iter().map(HeapSize::heap_size).sum::<usize>()
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_vec_heap_size() {
+        let strings = vec![String::from("hello"), String::new()];
+        let expected = strings.capacity() * size_of::<String>() + strings[0].capacity();
+
+        assert_eq!(strings.heap_size(), expected);
+    }
+}
@@ -42,9 +42,44 @@
 //!
 //! HashCow is licensed under the MIT license.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
 use hashbrown::HashMap;
-use std::borrow::{Borrow, Cow, ToOwned};
-use std::hash::Hash;
+use hashbrown::hash_map::DefaultHashBuilder;
+use alloc::borrow::{Cow, ToOwned};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::iter::FromIterator;
+use core::ops::{Index, RangeBounds};
+
+mod diff;
+mod entry;
+mod error;
+mod heap_size;
+mod raw_entry;
+mod shared;
+mod snapshot;
+
+#[cfg(feature = "bincode")]
+mod bincode_impls;
+
+#[cfg(feature = "rayon")]
+mod rayon_impls;
+
+#[cfg(feature = "serde")]
+mod serde_impls;
+
+pub use diff::MapDiff;
+pub use entry::{Entry, OccupiedEntry, OccupiedError, VacantEntry};
+pub use error::{CapacityExceeded, LengthMismatch};
+pub use heap_size::HeapSize;
+pub use raw_entry::{RawEntryBuilderMut, RawEntryMut, RawOccupiedEntryMut, RawVacantEntryMut};
+pub use snapshot::FormSnapshot;
+pub use hashbrown::TryReserveError;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 /// The form of the entry in the map. Can be either
@@ -57,15 +92,45 @@ pub enum Form {
     Owned,
 }
 
+impl Form {
+    /// Returns the form of the given `Cow`.
+    #[inline]
+    #[allow(clippy::ptr_arg)]
+    pub(crate) fn of<B: ToOwned + ?Sized>(cow: &Cow<'_, B>) -> Form {
+        match cow {
+            Cow::Borrowed(_) => Form::Borrowed,
+            Cow::Owned(_) => Form::Owned,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+/// The number of entries of a map in each `Form`.
+/// 
+/// This is constructed from the `form_summary` method on `CowHashMap`.
+pub struct FormSummary {
+    /// The number of entries whose value is borrowed.
+    pub borrowed: usize,
+
+    /// The number of entries whose value is owned.
+    pub owned: usize,
+
+    /// The total number of entries.
+    pub total: usize,
+}
+
 /// A HashMap data-structure with copy-on-write keys and values.
-pub struct CowHashMap<'a, K, V> 
+/// 
+/// The map uses the `hashbrown` default hasher unless a custom
+/// `BuildHasher` is supplied via `with_hasher` or `with_capacity_and_hasher`.
+pub struct CowHashMap<'a, K, V, S = DefaultHashBuilder> 
     where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
           V: ToOwned + ?Sized,
 {
-    inner: HashMap<Cow<'a, K>, Cow<'a, V>>
+    inner: HashMap<Cow<'a, K>, Cow<'a, V>, S>
 }
 
-impl<'a, K, V> CowHashMap<'a, K, V> 
+impl<'a, K, V> CowHashMap<'a, K, V, DefaultHashBuilder> 
     where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
           V: ToOwned + ?Sized,
 {
@@ -100,6 +165,157 @@ impl<'a, K, V> CowHashMap<'a, K, V>
         }
     }
 
+    /// Creates a new `CowHashMap` which borrows every key and value
+    /// from the given slice of owned pairs.
+    /// 
+    /// No data is cloned, so every entry of the resulting map is in
+    /// borrowed form. If the slice contains duplicate keys, the last
+    /// pair wins.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let pairs = vec![("key".to_owned(), vec![1, 2, 3])];
+    /// let hm: CowHashMap<str, [u8]> = CowHashMap::from_borrowed_pairs(&pairs);
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3]);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// ```
+    pub fn from_borrowed_pairs(pairs: &'a [(<K as ToOwned>::Owned, <V as ToOwned>::Owned)]) -> Self {
+        let mut hm = CowHashMap::new();
+        hm.extend_borrowed(pairs.iter().map(|(k, v)| (k.borrow(), v.borrow())));
+        hm
+    }
+
+    /// Creates a new `CowHashMap` from a `std::collections::HashMap`,
+    /// moving all of its entries into the map in owned form.
+    /// 
+    /// This function is only available with the `std` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut std_hm: HashMap<String, Vec<u8>> = HashMap::new();
+    /// std_hm.insert("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// let hm: CowHashMap<str, [u8]> = CowHashMap::from_std_hashmap(std_hm);
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3]);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn from_std_hashmap(map: std::collections::HashMap<<K as ToOwned>::Owned, <V as ToOwned>::Owned>) -> Self {
+        let mut hm = CowHashMap::with_capacity(map.len());
+
+        for (key, val) in map {
+            hm.insert_owned(key, val);
+        }
+
+        hm
+    }
+}
+
+impl<'a, K, V, S> CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Creates a new `CowHashMap` which will use the given hash builder to hash keys.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    /// 
+    /// let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    /// let mut hm: CowHashMap<str, [u8], _> = CowHashMap::with_hasher(hasher);
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn with_hasher(hasher: S) -> Self {
+        CowHashMap {
+            inner: HashMap::with_hasher(hasher)
+        }
+    }
+
+    /// Creates a new `CowHashMap` with the specified capacity, using
+    /// the given hash builder to hash keys.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use std::collections::hash_map::DefaultHasher;
+    /// use std::hash::BuildHasherDefault;
+    /// 
+    /// let hasher = BuildHasherDefault::<DefaultHasher>::default();
+    /// let hm: CowHashMap<str, [u8], _> = CowHashMap::with_capacity_and_hasher(5, hasher);
+    /// 
+    /// assert!(hm.capacity() >= 5);
+    /// ```
+    #[inline]
+    pub fn with_capacity_and_hasher(capacity: usize, hasher: S) -> Self {
+        CowHashMap {
+            inner: HashMap::with_capacity_and_hasher(capacity, hasher)
+        }
+    }
+
+    /// Creates a new `CowHashMap` wrapping the given `hashbrown` map of
+    /// `Cow` keys and values.
+    /// 
+    /// This allows building the map with arbitrary forms for each entry
+    /// before wrapping it. The entries keep their form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use hashbrown::HashMap;
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut inner: HashMap<Cow<str>, Cow<[u8]>> = HashMap::new();
+    /// inner.insert(Cow::Borrowed("key1"), Cow::Borrowed(&[1, 2, 3]));
+    /// inner.insert(Cow::Borrowed("key2"), Cow::Owned(vec![4, 5, 6]));
+    /// 
+    /// let hm = CowHashMap::from_cow_map(inner);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn from_cow_map(inner: HashMap<Cow<'a, K>, Cow<'a, V>, S>) -> Self {
+        CowHashMap { inner }
+    }
+
+    /// Consumes the map, returning the underlying `hashbrown` map of
+    /// `Cow` keys and values.
+    /// 
+    /// The entries keep their form. This is the inverse of `from_cow_map`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use std::borrow::Cow;
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let inner = hm.into_inner();
+    /// assert!(matches!(inner.get("key"), Some(Cow::Borrowed(_))));
+    /// ```
+    #[inline]
+    pub fn into_inner(self) -> HashMap<Cow<'a, K>, Cow<'a, V>, S> {
+        self.inner
+    }
+
+    /// Returns a reference to the map's `BuildHasher`.
+    #[inline]
+    pub fn hasher(&self) -> &S {
+        self.inner.hasher()
+    }
+
     /// Returns the number of elements the map can hold without reallocating.
     /// 
     /// This number is a lower bound; the map might be able to hold more elements, but is guaranteed to be able to hold at least this many elements.
@@ -123,13 +339,104 @@ impl<'a, K, V> CowHashMap<'a, K, V>
         self.inner.reserve(additional);
     }
 
+    /// Reserves capacity for at least additional more elements to be inserted
+    /// in the map, without the extra space `reserve` may set aside to avoid
+    /// frequent reallocations.
+    /// 
+    /// The underlying `hashbrown` map has no exact reservation, so this is a
+    /// best-effort `reserve` followed by `shrink_to` when the map had to grow.
+    /// The resulting capacity is at least `len + additional`, rounded up to the
+    /// next table size supported by `hashbrown`. An existing larger capacity
+    /// is left untouched.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.reserve_exact(10);
+    /// 
+    /// assert!(hm.capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let capacity = self.inner.capacity();
+        self.inner.reserve(additional);
+
+        if self.inner.capacity() > capacity {
+            self.inner.shrink_to(self.inner.len() + additional);
+        }
+    }
+
+    /// Tries to reserve capacity for at least additional more elements to be inserted in the map.
+    /// The collection may reserve more space to avoid frequent reallocations.
+    /// 
+    /// Unlike `reserve`, this function does not abort on allocation failure.
+    /// Instead, it returns a `TryReserveError`, which is either
+    /// `TryReserveError::CapacityOverflow` if the new capacity would overflow,
+    /// or `TryReserveError::AllocError` if the allocator reported a failure.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.try_reserve(10).expect("allocation failed");
+    /// 
+    /// assert!(hm.capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.inner.try_reserve(additional)
+    }
+
     /// Shrinks the map as much as possible while retaining the number of elements.
     #[inline]
     pub fn shrink_to_fit(&mut self) {
         self.inner.shrink_to_fit();
     }
 
+    /// Shrinks the capacity of the map with a lower limit. It will drop
+    /// down no lower than the supplied limit while maintaining the internal
+    /// rules and possibly leaving some space in accordance with the resize policy.
+    /// 
+    /// The capacity will remain at least as large as both the length and
+    /// the supplied value. This function does nothing if the current
+    /// capacity is smaller than the supplied minimum capacity.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::with_capacity(100);
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// hm.shrink_to(10);
+    /// assert!(hm.capacity() >= 10);
+    /// ```
+    #[inline]
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.inner.shrink_to(min_capacity);
+    }
+
     /// Clears the map, removing all key-value pairs. Keeps the allocated memory for reuse.
+    /// 
+    /// The capacity of the map is retained, so subsequent inserts will not
+    /// reallocate until the previous capacity is exceeded.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// let capacity = hm.capacity();
+    /// hm.clear();
+    /// 
+    /// assert!(hm.is_empty());
+    /// assert_eq!(hm.capacity(), capacity);
+    /// ```
     #[inline]
     pub fn clear(&mut self) {
         self.inner.clear();
@@ -152,6 +459,30 @@ impl<'a, K, V> CowHashMap<'a, K, V>
         self.len() == 0
     }
 
+    /// Inserts a new key/value pair into the map, storing the key
+    /// and value in the form in which they are given.
+    /// 
+    /// This function returns `None` if there was no value previously 
+    /// associated with the given key. If the key is replaced, this
+    /// function returns the previous value. If the previous value
+    /// is borrowed, it will be cloned and then returned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// use std::borrow::Cow;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert(Cow::Borrowed("key"), Cow::Owned(vec![1, 2, 3]));
+    ///
+    /// assert_eq!(hm.len(), 1);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn insert(&mut self, key: Cow<'a, K>, value: Cow<'a, V>) -> Option<<V as ToOwned>::Owned> {
+        self.inner.insert(key, value).map(|x| x.into_owned())
+    }
+
     /// Inserts a new key/value pair into the map with the value
     /// being in the owned form.
     /// 
@@ -171,7 +502,58 @@ impl<'a, K, V> CowHashMap<'a, K, V>
     /// ```
     #[inline]
     pub fn insert_owned(&mut self, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned) -> Option<<V as ToOwned>::Owned> {
-        self.inner.insert(Cow::Owned(key), Cow::Owned(value)).map(|x| x.into_owned())
+        self.insert(Cow::Owned(key), Cow::Owned(value))
+    }
+
+    /// Tries to insert an owned key and value into the map, and returns
+    /// a mutable reference to the value in the entry.
+    /// 
+    /// If the map already has a value for the key, nothing is updated and
+    /// an error containing the occupied entry and the value is returned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// 
+    /// assert_eq!(hm.try_insert_owned("key".to_owned(), vec![1]).unwrap(), &vec![1]);
+    /// 
+    /// let err = hm.try_insert_owned("key".to_owned(), vec![2]).unwrap_err();
+    /// assert_eq!(err.entry.get(), &[1]);
+    /// assert_eq!(err.value, vec![2]);
+    /// ```
+    #[inline]
+    pub fn try_insert_owned(&mut self, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned) -> Result<&mut <V as ToOwned>::Owned, OccupiedError<'_, 'a, K, V, S>> {
+        match self.entry(key) {
+            Entry::Occupied(entry) => Err(OccupiedError { entry, value }),
+            Entry::Vacant(entry) => Ok(entry.insert_owned(value)),
+        }
+    }
+
+    /// Inserts an owned key and value into the map, unless the key is not
+    /// present and the map already holds `max_len` entries.
+    /// 
+    /// Overwriting the value of an existing key is always allowed. On
+    /// success, the old value is returned as with `insert_owned`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{CapacityExceeded, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// 
+    /// assert_eq!(hm.insert_owned_capped("key1".to_owned(), vec![1], 1), Ok(None));
+    /// assert_eq!(hm.insert_owned_capped("key1".to_owned(), vec![2], 1), Ok(Some(vec![1])));
+    /// assert_eq!(hm.insert_owned_capped("key2".to_owned(), vec![3], 1), Err(CapacityExceeded { max_len: 1 }));
+    /// ```
+    #[inline]
+    pub fn insert_owned_capped(&mut self, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned, max_len: usize) -> Result<Option<<V as ToOwned>::Owned>, CapacityExceeded> {
+        if self.inner.len() >= max_len && !self.inner.contains_key(key.borrow()) {
+            return Err(CapacityExceeded { max_len });
+        }
+
+        Ok(self.insert_owned(key, value))
     }
 
     /// Inserts a new key/value pair into the map with the value
@@ -193,7 +575,7 @@ impl<'a, K, V> CowHashMap<'a, K, V>
     /// ```
     #[inline]
     pub fn insert_owned_borrowed_key(&mut self, key: &'a K, value: <V as ToOwned>::Owned) -> Option<<V as ToOwned>::Owned> {
-        self.inner.insert(Cow::Borrowed(key), Cow::Owned(value)).map(|x| x.into_owned())
+        self.insert(Cow::Borrowed(key), Cow::Owned(value))
     }
 
     /// Inserts a new key/value pair in to the map with the value
@@ -215,7 +597,7 @@ impl<'a, K, V> CowHashMap<'a, K, V>
     /// ```
     #[inline]
     pub fn insert_borrowed(&mut self, key: &'a K, value: &'a V) -> Option<<V as ToOwned>::Owned> {
-        self.inner.insert(Cow::Borrowed(key), Cow::Borrowed(value)).map(|x| x.into_owned())
+        self.insert(Cow::Borrowed(key), Cow::Borrowed(value))
     }
 
     /// Inserts a new key/value pair in to the map with the value
@@ -237,214 +619,4555 @@ impl<'a, K, V> CowHashMap<'a, K, V>
     /// ```
     #[inline]
     pub fn insert_borrowed_owned_key(&mut self, key: <K as ToOwned>::Owned, value: &'a V) -> Option<<V as ToOwned>::Owned> {
-        self.inner.insert(Cow::Owned(key), Cow::Borrowed(value)).map(|x| x.into_owned())
+        self.insert(Cow::Owned(key), Cow::Borrowed(value))
     }
 
-    /// Attempts to retrieve a reference to an item stored in the map.
+    /// Inserts all of the key/value pairs yielded by the iterator, with
+    /// both the keys and values being in borrowed form.
+    /// 
+    /// No data is cloned when inserting. Existing keys are replaced in
+    /// the same manner as `insert_borrowed`.
     /// 
     /// ## Example
     /// ```rust
-    /// use hashcow::CowHashMap;
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let keys = ["key1", "key2"];
+    /// let vals = [vec![1, 2, 3], vec![4, 5, 6]];
     /// 
     /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
-    /// hm.insert_borrowed("key1", &[1, 2, 3]);
-    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
-    ///
+    /// hm.extend_borrowed(keys.iter().cloned().zip(vals.iter().map(|v| v.as_slice())));
+    /// 
     /// assert_eq!(hm.len(), 2);
-    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3]);
-    /// assert_eq!(hm.get(&"key2").unwrap(), &[4, 5, 6]);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
     /// ```
-    #[inline]
-    pub fn get(&self, key: &K) -> Option<&V> {
-        self.inner.get(key).map(|v| v.as_ref())
+    pub fn extend_borrowed<I>(&mut self, iter: I) 
+        where I: IntoIterator<Item = (&'a K, &'a V)>
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for (key, val) in iter {
+            self.insert_borrowed(key, val);
+        }
     }
 
-    /// Attempts to retrieve a mutable reference to the owned
-    /// form of an item stored in the map. 
+    /// Inserts the keys and values of two parallel slices as borrowed
+    /// entries, pairing each key with the value at the same index.
     /// 
-    /// If the stored entry is in the borrowed form, this function
-    /// will clone the underlying data.
+    /// If the slices have different lengths, nothing is inserted and a
+    /// `LengthMismatch` error is returned. Existing keys are replaced in
+    /// the same manner as `insert_borrowed`.
     /// 
     /// ## Example
     /// ```rust
     /// use hashcow::{Form, CowHashMap};
     /// 
-    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
-    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// let keys = vec!["key1".to_owned(), "key2".to_owned()];
+    /// let vals = vec![vec![1, 2, 3], vec![4, 5, 6]];
     /// 
-    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_zip_borrowed(&keys, &vals).unwrap();
     /// 
-    /// {
-    ///     // This will clone the entry stored at this key
-    ///     let entry = hm.get_mut(&"key1").unwrap();
-    ///     assert_eq!(entry, &mut vec![1, 2, 3]);
-    ///     
-    ///     *entry = vec![4, 5, 6];
-    /// }
-    ///
-    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
-    /// assert_eq!(hm.get(&"key1").unwrap(), &[4, 5, 6]);
+    /// assert_eq!(hm.get(&"key2").unwrap(), &[4, 5, 6]);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
     /// ```
-    #[inline]
-    pub fn get_mut(&mut self, key: &K) -> Option<&mut <V as ToOwned>::Owned> {
-        self.inner.get_mut(key).map(|v| v.to_mut())
+    pub fn insert_zip_borrowed(&mut self, keys: &'a [<K as ToOwned>::Owned], vals: &'a [<V as ToOwned>::Owned]) -> Result<(), LengthMismatch> {
+        if keys.len() != vals.len() {
+            return Err(LengthMismatch { keys: keys.len(), values: vals.len() });
+        }
+
+        self.extend_borrowed(keys.iter().map(Borrow::borrow).zip(vals.iter().map(Borrow::borrow)));
+        Ok(())
     }
 
-    #[inline]
-    /// Returns an iterator over the keys of the map.
+    /// Inserts all of the given `Cow` key-value pairs into the map,
+    /// preserving the form of each key and value.
     /// 
     /// ## Example
     /// ```rust
-    /// # #[macro_use] extern crate hashcow; fn main() {
-    /// # use std::collections::HashSet;
-    /// use hashcow::CowHashMap;
+    /// use std::borrow::Cow;
+    /// use hashcow::{Form, CowHashMap};
     /// 
     /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
-    /// hm.insert_borrowed("key1", &[1, 2, 3]);
-    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// hm.extend_cow(vec![
+    ///     (Cow::Borrowed("key1"), Cow::Borrowed(&[1, 2, 3][..])),
+    ///     (Cow::Owned("key2".to_owned()), Cow::Owned(vec![4, 5, 6])),
+    /// ]);
     /// 
-    /// let keys: HashSet<&str> = hm.keys().collect();
-    /// assert_eq!(keys, set!["key1", "key2"]);
-    /// # }
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Owned);
     /// ```
-    #[inline]
-    pub fn keys(&self) -> impl Iterator<Item = &K> {
-        self.inner.keys().map(|k| k.borrow())
+    pub fn extend_cow<I>(&mut self, iter: I) 
+        where I: IntoIterator<Item = (Cow<'a, K>, Cow<'a, V>)>
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for (key, val) in iter {
+            self.insert(key, val);
+        }
     }
 
-    /// Makes a specific value in the map owned, if it isn't so already.
+    /// Inserts all of the given owned key-value pairs into the map,
+    /// reserving capacity for all of them up front.
     /// 
-    /// This function does not do anything if the value is already in owned
-    /// form.
-    #[inline]
-    pub fn make_owned(&mut self, key: &K) -> Option<&V> {
-        let val = self.inner.get_mut(key)?;
-        
-        match val {
-            Cow::Borrowed(v) => {
-                *val = Cow::Owned(v.to_owned());
-                self.inner.get(key).map(|v| v.as_ref())
-            }
+    /// Since the exact number of pairs is known, the map reallocates
+    /// at most once.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.extend_owned_reserved(vec![("key1".to_owned(), vec![1]), ("key2".to_owned(), vec![2])]);
+    /// 
+    /// assert_eq!(hm.len(), 2);
+    /// ```
+    pub fn extend_owned_reserved<I>(&mut self, iter: I) 
+        where I: IntoIterator<Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned)>,
+              I::IntoIter: ExactSizeIterator,
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.len());
 
-            Cow::Owned(_) => {
-                self.inner.get(key).map(|v| v.as_ref())
-            }
+        for (key, val) in iter {
+            self.insert_owned(key, val);
         }
     }
 
-    /// Returns the number of elements that are currently in the map.
+    /// Attempts to retrieve a reference to an item stored in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    ///
+    /// assert_eq!(hm.len(), 2);
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3]);
+    /// assert_eq!(hm.get(&"key2").unwrap(), &[4, 5, 6]);
+    /// ```
     #[inline]
-    pub fn len(&self) -> usize {
-        self.inner.len()
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.inner.get(key).map(|v| v.as_ref())
     }
 
-    /// If an entry with the given key exists, this function
-    /// returns the underlying form in which it is stored in 
-    /// the map.
+    /// Attempts to retrieve the stored key and a reference to the value
+    /// corresponding to the given key.
     /// 
-    /// Can be either `Form::Borrowed` or `Form::Owned`.
+    /// The returned key reference points to the key held by the map, rather
+    /// than to the key that was used for the lookup.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    ///
+    /// assert_eq!(hm.get_key_value(&"key1").unwrap(), ("key1", &[1, 2, 3][..]));
+    /// assert!(hm.get_key_value(&"key2").is_none());
+    /// ```
     #[inline]
-    pub fn entry_form(&self, key: &K) -> Option<Form> {
-        let val = self.inner.get(key)?;
+    pub fn get_key_value(&self, key: &K) -> Option<(&K, &V)> {
+        self.inner.get_key_value(key).map(|(k, v)| (k.borrow(), v.as_ref()))
+    }
 
-        match val {
-            Cow::Borrowed(_) => Some(Form::Borrowed),
-            Cow::Owned(_) => Some(Form::Owned),
+    /// Returns true if the map contains a value for the specified key.
+    /// 
+    /// This does not alter the form of the stored entry.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    ///
+    /// assert!(hm.contains_key(&"key1"));
+    /// assert!(!hm.contains_key(&"key2"));
+    /// ```
+    #[inline]
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.inner.contains_key(key)
+    }
+
+    /// Returns true if the map contains the specified value, regardless
+    /// of the form in which it is stored.
+    /// 
+    /// This scans every entry of the map, so it takes linear time.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    ///
+    /// assert!(hm.contains_value(&[1, 2, 3]));
+    /// assert!(!hm.contains_value(&[4, 5, 6]));
+    /// ```
+    #[inline]
+    pub fn contains_value(&self, value: &V) -> bool 
+        where V: PartialEq
+    {
+        self.inner.values().any(|v| v.as_ref() == value)
+    }
+
+    /// Returns a key whose value equals the specified value, regardless
+    /// of the form in which the value is stored.
+    /// 
+    /// This scans the entries of the map, so it takes linear time. Since
+    /// the iteration order is unspecified, any of the matching keys may
+    /// be returned if more than one key maps to the value.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    ///
+    /// assert_eq!(hm.find_key_by_value(&[1, 2, 3]), Some("key1"));
+    /// assert_eq!(hm.find_key_by_value(&[4, 5, 6]), None);
+    /// ```
+    #[inline]
+    pub fn find_key_by_value(&self, value: &V) -> Option<&K> 
+        where V: PartialEq
+    {
+        self.inner
+            .iter()
+            .find(|(_, v)| v.as_ref() == value)
+            .map(|(k, _)| k.borrow())
+    }
+
+    /// Groups the keys of the map whose values are equal, regardless of
+    /// the form in which the values are stored.
+    /// 
+    /// Only groups of two or more keys are returned, so an empty result
+    /// means that every value in the map is unique. The order of the
+    /// groups and of the keys within each group is unspecified. This is
+    /// useful for deciding whether sharing values through `Arc` would be
+    /// worthwhile, and does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![1, 2, 3]);
+    /// hm.insert_owned("key3".to_owned(), vec![4, 5, 6]);
+    ///
+    /// let groups = hm.duplicate_value_groups();
+    /// assert_eq!(groups.len(), 1);
+    /// assert_eq!(groups[0].len(), 2);
+    /// ```
+    pub fn duplicate_value_groups(&self) -> Vec<Vec<&K>> 
+        where V: Eq + Hash
+    {
+        let mut groups: HashMap<&V, Vec<&K>> = HashMap::new();
+
+        for (key, val) in self.iter() {
+            groups.entry(val).or_default().push(key);
         }
+
+        groups
+            .into_values()
+            .filter(|keys| keys.len() > 1)
+            .collect()
     }
 
-    /// Returns a cloned version of the map but with
-    /// the entries in borrowed form.
+    /// Attempts to retrieve a mutable reference to the owned
+    /// form of an item stored in the map. 
+    /// 
+    /// If the stored entry is in the borrowed form, this function
+    /// will clone the underlying data.
     /// 
     /// ## Example
     /// ```rust
     /// use hashcow::{Form, CowHashMap};
     /// 
     /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
-    /// hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
     /// 
-    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
     /// 
-    /// let hm_clone = hm.borrow_fields();
-    /// assert_eq!(hm_clone.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// {
+    ///     // This will clone the entry stored at this key
+    ///     let entry = hm.get_mut(&"key1").unwrap();
+    ///     assert_eq!(entry, &mut vec![1, 2, 3]);
+    ///     
+    ///     *entry = vec![4, 5, 6];
+    /// }
+    ///
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[4, 5, 6]);
     /// ```
     #[inline]
-    pub fn borrow_fields(&'a self) -> Self {
-        let collection: HashMap<Cow<'a, K>, Cow<'a, V>> = self.inner
-            .iter()
-            .map(|(k, v)| {
-                match (k, v) {
-                    (Cow::Owned(key), Cow::Owned(val)) => {
-                        (Cow::Borrowed((*key).borrow()), Cow::Borrowed((*val).borrow()))
-                    }
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut <V as ToOwned>::Owned> {
+        self.inner.get_mut(key).map(|v| v.to_mut())
+    }
 
-                    (Cow::Borrowed(key), Cow::Owned(val)) => {
-                        (Cow::Borrowed(*key), Cow::Borrowed((*val).borrow()))
-                    }
+    /// Returns a mutable reference to the value stored at the given key,
+    /// but only if it is already stored in owned form.
+    /// 
+    /// Unlike `get_mut`, this never clones a borrowed value. If the value
+    /// is borrowed or the key is not present in the map, `None` is returned
+    /// and the form of the entry is left untouched.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert!(hm.get_mut_if_owned(&"key1").is_none());
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// 
+    /// hm.get_mut_if_owned(&"key2").unwrap().push(7);
+    /// assert_eq!(hm.get(&"key2").unwrap(), &[4, 5, 6, 7]);
+    /// ```
+    #[inline]
+    pub fn get_mut_if_owned(&mut self, key: &K) -> Option<&mut <V as ToOwned>::Owned> {
+        match self.inner.get_mut(key) {
+            Some(Cow::Owned(v)) => Some(v),
+            _ => None,
+        }
+    }
 
-                    (Cow::Owned(key), Cow::Borrowed(val)) => {
-                        (Cow::Borrowed((*key).borrow()), Cow::Borrowed(*val))
-                    }
+    /// Returns a reference to the stored key along with a mutable
+    /// reference to the owned form of the value stored at the key.
+    /// 
+    /// If the stored entry is in the borrowed form, this function
+    /// will clone the underlying data. The key is left in its form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// let (key, val) = hm.get_pair_mut(&"key1").unwrap();
+    /// val.push(key.len() as u8);
+    /// 
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3, 4]);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn get_pair_mut(&mut self, key: &K) -> Option<(&K, &mut <V as ToOwned>::Owned)> {
+        self.inner
+            .get_key_value_mut(key)
+            .map(|(k, v)| (k.borrow(), v.to_mut()))
+    }
 
-                    (Cow::Borrowed(key), Cow::Borrowed(val)) => {
-                        (Cow::Borrowed(*key), Cow::Borrowed(*val))
-                    }
-                }
-                
-            })
-            .collect();
+    /// Calls `f` with a mutable reference to the owned form of the value
+    /// stored at the given key, returning `false` if the key is not present.
+    /// 
+    /// If the stored entry is in the borrowed form, this function will
+    /// clone the underlying data once before calling `f`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert!(hm.update_in_place(&"key1", |v| v.push(4)));
+    /// assert!(!hm.update_in_place(&"key2", |v| v.push(4)));
+    /// 
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3, 4]);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn update_in_place<F>(&mut self, key: &K, mut f: F) -> bool 
+        where F: FnMut(&mut <V as ToOwned>::Owned)
+    {
+        match self.inner.get_mut(key) {
+            Some(val) => {
+                f(val.to_mut());
+                true
+            }
 
-        CowHashMap { inner: collection }
+            None => false,
+        }
     }
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
+    /// Attempts to retrieve mutable references to the owned form of `N`
+    /// items stored in the map at once.
+    /// 
+    /// Returns `None` if any of the keys are missing or if the same key
+    /// is given more than once. If any of the targeted entries are in the
+    /// borrowed form, this function will clone their underlying data.
     /// 
     /// ## Example
     /// ```rust
-    /// # #[macro_use] extern crate hashcow; fn main() {
-    /// # use std::collections::HashSet;
     /// use hashcow::CowHashMap;
     /// 
     /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
     /// hm.insert_borrowed("key1", &[1, 2, 3]);
     /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
     /// 
-    /// for (key, val) in hm.iter() {
-    ///     // ...
-    /// }
-    /// # }
+    /// let [val1, val2] = hm.get_many_mut([&"key1", &"key2"]).unwrap();
+    /// std::mem::swap(val1, val2);
+    /// 
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[4, 5, 6]);
+    /// assert!(hm.get_many_mut([&"key1", &"key1"]).is_none());
     /// ```
     #[inline]
-    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
-        self.inner.iter().map(|(k, v)| (k.borrow(), v.borrow()))
+    pub fn get_many_mut<const N: usize>(&mut self, keys: [&K; N]) -> Option<[&mut <V as ToOwned>::Owned; N]> {
+        self.inner
+            .get_many_mut(keys)
+            .map(|vals| vals.map(|v| v.to_mut()))
     }
 
-    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    /// Returns references to the values stored at each of the given
+    /// keys, in the same order as the keys.
+    /// 
+    /// Keys which are not present in the map yield `None`. Unlike
+    /// `get_many_mut`, this does not alter the form of any entry, and
+    /// the keys need not be distinct.
     /// 
     /// ## Example
     /// ```rust
     /// use hashcow::CowHashMap;
     /// 
     /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
-    /// assert!(hm.remove(&"key1").is_none());
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// let [a, b] = hm.get_multi([&"key1", &"key2"]);
+    /// assert_eq!(a, Some(&[1, 2, 3][..]));
+    /// assert_eq!(b, None);
+    /// ```
+    #[inline]
+    pub fn get_multi<const N: usize>(&self, keys: [&K; N]) -> [Option<&V>; N] {
+        keys.map(|key| self.get(key))
+    }
+
+    /// Returns a mutable reference to the owned form of the value stored
+    /// at the given key, inserting the owned value computed by `f` if
+    /// the key is not present.
+    /// 
+    /// If the stored entry is in the borrowed form, this function will
+    /// clone the underlying data. `f` is only called if the key is absent.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
     /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
     /// hm.insert_borrowed("key1", &[1, 2, 3]);
-    /// assert_eq!(hm.remove(&"key1").unwrap(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.get_or_insert_with("key1".to_owned(), || vec![0]), &vec![1, 2, 3]);
+    /// assert_eq!(hm.get_or_insert_with("key2".to_owned(), || vec![0]), &vec![0]);
     /// ```
-    pub fn remove(&mut self, key: &K) -> Option<<V as ToOwned>::Owned> {
-        let val = self.inner.remove(key)?;
+    #[inline]
+    pub fn get_or_insert_with<F>(&mut self, key: <K as ToOwned>::Owned, f: F) -> &mut <V as ToOwned>::Owned 
+        where F: FnOnce() -> <V as ToOwned>::Owned
+    {
+        self.inner
+            .entry(Cow::Owned(key))
+            .or_insert_with(|| Cow::Owned(f()))
+            .to_mut()
+    }
 
-        match val {
-            Cow::Borrowed(val) => Some(val.to_owned()),
-            Cow::Owned(val) => Some(val),
-        }
+    /// Returns a mutable reference to the owned form of the value stored
+    /// at the given key, inserting the default owned value if the key is
+    /// not present.
+    /// 
+    /// If the stored entry is in the borrowed form, this function will
+    /// clone the underlying data.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, u64> = CowHashMap::new();
+    /// 
+    /// *hm.entry_or_default("key".to_owned()) += 1;
+    /// *hm.entry_or_default("key".to_owned()) += 1;
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &2);
+    /// ```
+    #[inline]
+    pub fn entry_or_default(&mut self, key: <K as ToOwned>::Owned) -> &mut <V as ToOwned>::Owned 
+        where <V as ToOwned>::Owned: Default
+    {
+        self.get_or_insert_with(key, Default::default)
     }
-}
 
-#[macro_use]
-mod macros;
+    /// Returns a reference to the value stored at the given key,
+    /// inserting the given borrowed value if the key is not present.
+    /// 
+    /// This function does not alter the form of an existing entry, so
+    /// no data is cloned on either path.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.get_or_insert_borrowed("key1".to_owned(), &[0]), &[1, 2, 3]);
+    /// assert_eq!(hm.get_or_insert_borrowed("key2".to_owned(), &[0]), &[0]);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn get_or_insert_borrowed(&mut self, key: <K as ToOwned>::Owned, value: &'a V) -> &V {
+        self.inner
+            .entry(Cow::Owned(key))
+            .or_insert(Cow::Borrowed(value))
+    }
 
-#[cfg(test)]
+    /// Returns a reference to the value stored at the given key,
+    /// inserting the given borrowed key and value if the key is not
+    /// present.
+    /// 
+    /// Unlike `get_or_insert_borrowed`, the key is borrowed as well, so
+    /// no data is cloned on either path. This function does not alter
+    /// the form of an existing entry.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// 
+    /// assert_eq!(hm.get_or_insert_ref("key", &[1, 2, 3]), &[1, 2, 3]);
+    /// assert_eq!(hm.get_or_insert_ref("key", &[4, 5, 6]), &[1, 2, 3]);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn get_or_insert_ref(&mut self, key: &'a K, default: &'a V) -> &V {
+        self.inner
+            .entry(Cow::Borrowed(key))
+            .or_insert(Cow::Borrowed(default))
+    }
+
+    /// Attempts to retrieve an owned clone of an item stored in the map.
+    /// 
+    /// Unlike `get_mut`, this does not alter the form of the stored entry.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.get_cloned(&"key1").unwrap(), vec![1, 2, 3]);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn get_cloned(&self, key: &K) -> Option<<V as ToOwned>::Owned> {
+        self.inner.get(key).map(|v| v.as_ref().to_owned())
+    }
+
+    /// Returns an iterator over the keys of the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// # #[macro_use] extern crate hashcow; fn main() {
+    /// # use std::collections::HashSet;
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let keys: HashSet<&str> = hm.keys().collect();
+    /// assert_eq!(keys, set!["key1", "key2"]);
+    /// # }
+    /// ```
+    #[inline]
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.inner.keys().map(|k| k.borrow())
+    }
+
+    /// Returns an iterator over owned clones of the keys of the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// let keys: Vec<String> = hm.keys_owned().collect();
+    /// assert_eq!(keys, vec!["key1".to_owned()]);
+    /// ```
+    #[inline]
+    pub fn keys_owned(&self) -> impl Iterator<Item = <K as ToOwned>::Owned> + '_ {
+        self.inner.keys().map(|k| k.as_ref().to_owned())
+    }
+
+    /// Returns an iterator over the values of the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let total_len: usize = hm.values().map(|v| v.len()).sum();
+    /// assert_eq!(total_len, 6);
+    /// ```
+    #[inline]
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.inner.values().map(|v| v.as_ref())
+    }
+
+    /// Returns an iterator over mutable references to the owned
+    /// form of the values of the map.
+    /// 
+    /// Each borrowed value is cloned when it is yielded by the iterator,
+    /// in the same manner as `get_mut`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// for val in hm.values_mut() {
+    ///     val.push(4);
+    /// }
+    /// 
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3, 4]);
+    /// ```
+    #[inline]
+    pub fn values_mut(&mut self) -> ValuesMut<'_, 'a, K, V> {
+        ValuesMut { inner: self.inner.values_mut() }
+    }
+
+    /// Creates a consuming iterator visiting all keys in arbitrary order.
+    /// 
+    /// Borrowed keys are cloned so that every yielded key is owned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let keys: Vec<String> = hm.into_keys().collect();
+    /// assert_eq!(keys, vec!["key".to_owned()]);
+    /// ```
+    #[inline]
+    pub fn into_keys(self) -> IntoKeys<'a, K, V> {
+        IntoKeys { inner: self.inner.into_keys() }
+    }
+
+    /// Creates a consuming iterator visiting all values in arbitrary order.
+    /// 
+    /// Borrowed values are cloned so that every yielded value is owned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let values: Vec<Vec<u8>> = hm.into_values().collect();
+    /// assert_eq!(values, vec![vec![1, 2, 3]]);
+    /// ```
+    #[inline]
+    pub fn into_values(self) -> IntoValues<'a, K, V> {
+        IntoValues { inner: self.inner.into_values() }
+    }
+
+    /// Gets the given key's corresponding entry in the map for in-place manipulation.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// hm.entry("key1".to_owned()).and_modify(|v| v.push(4));
+    /// hm.entry("key2".to_owned()).or_insert_borrowed(&[5, 6]);
+    /// 
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[1, 2, 3, 4]);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn entry(&mut self, key: <K as ToOwned>::Owned) -> Entry<'_, 'a, K, V, S> {
+        match self.inner.entry(Cow::Owned(key)) {
+            hashbrown::hash_map::Entry::Occupied(inner) => Entry::Occupied(OccupiedEntry { inner }),
+            hashbrown::hash_map::Entry::Vacant(inner) => Entry::Vacant(VacantEntry { inner }),
+        }
+    }
+
+    /// Gets the given borrowed key's corresponding entry in the map for
+    /// in-place manipulation.
+    /// 
+    /// Unlike `entry`, the key is never cloned: if a value is inserted
+    /// through the returned entry, the key is stored in borrowed form, and
+    /// if the entry is occupied, the stored key is left as it is.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// 
+    /// hm.entry_ref("key").or_insert_owned(vec![1, 2, 3]).push(4);
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3, 4]);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn entry_ref(&mut self, key: &'a K) -> Entry<'_, 'a, K, V, S> {
+        match self.inner.entry(Cow::Borrowed(key)) {
+            hashbrown::hash_map::Entry::Occupied(inner) => Entry::Occupied(OccupiedEntry { inner }),
+            hashbrown::hash_map::Entry::Vacant(inner) => Entry::Vacant(VacantEntry { inner }),
+        }
+    }
+
+    /// Creates a raw entry builder for the map.
+    /// 
+    /// Raw entries allow looking up an entry by a precomputed hash,
+    /// so that the hash of a key is not recomputed when probing
+    /// and then inserting into the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use core::hash::BuildHasher;
+    /// use hashcow::{Form, CowHashMap, RawEntryMut};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// let hash = hm.hasher().hash_one("key");
+    /// 
+    /// match hm.raw_entry_mut().from_key_hashed_nocheck(hash, &"key") {
+    ///     RawEntryMut::Occupied(_) => unreachable!(),
+    ///     RawEntryMut::Vacant(entry) => {
+    ///         entry.insert_borrowed_hashed_nocheck(hash, "key", &[1, 2, 3]);
+    ///     }
+    /// }
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3]);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn raw_entry_mut(&mut self) -> RawEntryBuilderMut<'_, 'a, K, V, S> {
+        RawEntryBuilderMut { inner: self.inner.raw_entry_mut() }
+    }
+
+    /// Makes a specific value in the map owned, if it isn't so already.
+    /// 
+    /// This function does not do anything if the value is already in owned
+    /// form.
+    #[inline]
+    pub fn make_owned(&mut self, key: &K) -> Option<&V> {
+        let val = self.inner.get_mut(key)?;
+        
+        match val {
+            Cow::Borrowed(v) => {
+                *val = Cow::Owned(v.to_owned());
+                self.inner.get(key).map(|v| v.as_ref())
+            }
+
+            Cow::Owned(_) => {
+                self.inner.get(key).map(|v| v.as_ref())
+            }
+        }
+    }
+
+    /// Makes a specific value in the map borrowed, by replacing it with
+    /// the given reference.
+    /// 
+    /// Since the map cannot borrow from its own owned data, the caller
+    /// must supply the reference to borrow from. Any owned value previously
+    /// stored at the key is dropped.
+    /// 
+    /// Returns `true` if an entry with the given key exists, and `false` otherwise.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let backing = vec![1, 2, 3];
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert!(hm.make_borrowed(&"key1", &backing));
+    /// assert!(!hm.make_borrowed(&"key2", &backing));
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn make_borrowed(&mut self, key: &K, value: &'a V) -> bool {
+        match self.inner.get_mut(key) {
+            Some(val) => {
+                *val = Cow::Borrowed(value);
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    /// Sets the form of the value stored at the given key.
+    /// 
+    /// Requesting `Form::Owned` behaves like `make_owned`, cloning the
+    /// value if it is borrowed. Requesting `Form::Borrowed` does not
+    /// alter the entry, since the map cannot borrow from its own owned
+    /// data; use `make_borrowed` and supply a reference instead.
+    /// 
+    /// Returns `true` if an entry with the given key exists, and `false` otherwise.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert!(hm.set_entry_form(&"key1", Form::Owned));
+    /// assert!(!hm.set_entry_form(&"key2", Form::Owned));
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn set_entry_form(&mut self, key: &K, form: Form) -> bool {
+        match form {
+            Form::Owned => self.make_owned(key).is_some(),
+            Form::Borrowed => self.inner.contains_key(key),
+        }
+    }
+
+    /// Makes every key and value in the map owned, cloning any
+    /// borrowed data.
+    /// 
+    /// After calling this function, the map no longer borrows from
+    /// any other data.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// hm.make_all_owned();
+    /// 
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Owned);
+    /// ```
+    pub fn make_all_owned(&mut self) {
+        let has_borrowed_keys = self.inner
+            .keys()
+            .any(|k| match k {
+                Cow::Borrowed(_) => true,
+                Cow::Owned(_) => false,
+            });
+
+        if has_borrowed_keys {
+            // Keys cannot be mutated in place so we re-insert all entries.
+            let entries: Vec<_> = self.inner
+                .drain()
+                .map(|(k, v)| (Cow::Owned(k.into_owned()), Cow::Owned(v.into_owned())))
+                .collect();
+
+            self.inner.extend(entries);
+        } else {
+            for val in self.inner.values_mut() {
+                val.to_mut();
+            }
+        }
+    }
+
+    /// Makes every key and value in the map owned, and then shrinks
+    /// the capacity of the map as much as possible.
+    /// 
+    /// After calling this function, the map no longer borrows from any
+    /// other data and uses a minimal allocation, which makes it suitable
+    /// for storing long-term.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::with_capacity(100);
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// hm.compact();
+    /// 
+    /// assert_eq!(hm.borrowed_count(), 0);
+    /// assert!(hm.capacity() < 100);
+    /// ```
+    #[inline]
+    pub fn compact(&mut self) {
+        self.make_all_owned();
+        self.shrink_to_fit();
+    }
+
+    /// Makes the borrowed values for which the predicate returns `true`
+    /// owned, and returns the number of converted entries.
+    /// 
+    /// The predicate is only called for borrowed values. Owned values and
+    /// keys are left untouched.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_borrowed("key2", &[4]);
+    /// 
+    /// assert_eq!(hm.materialize_if(|_, v| v.len() > 1), 1);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Owned);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    pub fn materialize_if<F>(&mut self, mut f: F) -> usize 
+        where F: FnMut(&K, &V) -> bool
+    {
+        let mut converted = 0;
+
+        for (key, val) in self.inner.iter_mut() {
+            if let Cow::Borrowed(v) = val {
+                if f(key.borrow(), v) {
+                    val.to_mut();
+                    converted += 1;
+                }
+            }
+        }
+
+        converted
+    }
+
+    /// Makes the owned values of the map borrowed again, using the
+    /// references returned by `source`, and returns the number of
+    /// converted entries.
+    /// 
+    /// `source` is called with the key of every owned entry. If it returns
+    /// a reference, the owned value is dropped and replaced by the borrowed
+    /// one, otherwise the entry is left untouched. Entries that are already
+    /// borrowed are not visited. The capacity of the map is not affected.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let original = vec![1, 2, 3];
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &original);
+    /// hm.make_owned(&"key1");
+    /// 
+    /// assert_eq!(hm.rebind_borrowed(|_| Some(&original)), 1);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// ```
+    pub fn rebind_borrowed<F>(&mut self, mut source: F) -> usize 
+        where F: FnMut(&K) -> Option<&'a V>
+    {
+        let mut converted = 0;
+
+        for (key, val) in self.inner.iter_mut() {
+            if let Cow::Owned(_) = val {
+                if let Some(borrowed) = source(key.borrow()) {
+                    *val = Cow::Borrowed(borrowed);
+                    converted += 1;
+                }
+            }
+        }
+
+        converted
+    }
+
+    /// Returns the number of elements that are currently in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inner.len()
+    }
+
+    /// If an entry with the given key exists, this function
+    /// returns the underlying form in which it is stored in 
+    /// the map.
+    /// 
+    /// Can be either `Form::Borrowed` or `Form::Owned`.
+    #[inline]
+    pub fn entry_form(&self, key: &K) -> Option<Form> {
+        self.inner.get(key).map(Form::of)
+    }
+
+    /// Returns the form in which the value stored at the given key is
+    /// stored, or `default` if the key is not present in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.form_or(&"key1", Form::Owned), Form::Borrowed);
+    /// assert_eq!(hm.form_or(&"key2", Form::Owned), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn form_or(&self, key: &K, default: Form) -> Form {
+        self.entry_form(key).unwrap_or(default)
+    }
+
+    /// Returns a reference to the value stored at the given key along
+    /// with the form in which it is stored.
+    /// 
+    /// This does not alter the form of the stored entry.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.peek(&"key1"), Some((&[1, 2, 3][..], Form::Borrowed)));
+    /// assert_eq!(hm.peek(&"key2"), None);
+    /// ```
+    #[inline]
+    pub fn peek(&self, key: &K) -> Option<(&V, Form)> {
+        self.inner.get(key).map(|v| (v.as_ref(), Form::of(v)))
+    }
+
+    /// Returns the form of the value stored at each of the given keys,
+    /// in the same order as the keys.
+    /// 
+    /// Keys which are not present in the map yield `None`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert_eq!(
+    ///     hm.forms_of(vec!["key1", "key2", "key3"]),
+    ///     vec![Some(Form::Borrowed), Some(Form::Owned), None]
+    /// );
+    /// ```
+    #[inline]
+    pub fn forms_of<'k, I>(&self, keys: I) -> Vec<Option<Form>> 
+        where I: IntoIterator<Item = &'k K>,
+              K: 'k,
+    {
+        keys.into_iter().map(|key| self.entry_form(key)).collect()
+    }
+
+    /// Returns the number of entries whose value is stored in owned form.
+    /// 
+    /// As with `entry_form`, only the form of the values is taken into
+    /// account. The sum of `owned_count` and `borrowed_count` is always
+    /// equal to `len`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert_eq!(hm.owned_count(), 1);
+    /// ```
+    #[inline]
+    pub fn owned_count(&self) -> usize {
+        self.inner
+            .values()
+            .filter(|v| Form::of(v) == Form::Owned)
+            .count()
+    }
+
+    /// Returns the number of entries whose value is stored in borrowed form.
+    /// 
+    /// As with `entry_form`, only the form of the values is taken into
+    /// account. The sum of `owned_count` and `borrowed_count` is always
+    /// equal to `len`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert_eq!(hm.borrowed_count(), 1);
+    /// ```
+    #[inline]
+    pub fn borrowed_count(&self) -> usize {
+        self.len() - self.owned_count()
+    }
+
+    /// Returns the number of borrowed and owned entries of the map,
+    /// computed in a single pass.
+    /// 
+    /// As with `entry_form`, only the form of the values is taken into
+    /// account.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{CowHashMap, FormSummary};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert_eq!(hm.form_summary(), FormSummary { borrowed: 1, owned: 1, total: 2 });
+    /// ```
+    pub fn form_summary(&self) -> FormSummary {
+        let mut summary = FormSummary { borrowed: 0, owned: 0, total: 0 };
+
+        for val in self.inner.values() {
+            match Form::of(val) {
+                Form::Borrowed => summary.borrowed += 1,
+                Form::Owned => summary.owned += 1,
+            }
+
+            summary.total += 1;
+        }
+
+        summary
+    }
+
+    /// Folds the borrowed and the owned entries of the map into two
+    /// separate accumulators in a single pass, returning
+    /// `(borrowed, owned)`.
+    /// 
+    /// As with `entry_form`, only the form of the values is taken into
+    /// account. This does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5]);
+    /// 
+    /// let (borrowed, owned) = hm.fold_by_form(0, 0, |acc, _, v| acc + v.len());
+    /// assert_eq!((borrowed, owned), (3, 2));
+    /// ```
+    pub fn fold_by_form<B, F>(&self, init_borrowed: B, init_owned: B, mut f: F) -> (B, B) 
+        where F: FnMut(B, &K, &V) -> B
+    {
+        self.inner
+            .iter()
+            .fold((init_borrowed, init_owned), |(borrowed, owned), (key, val)| {
+                match Form::of(val) {
+                    Form::Borrowed => (f(borrowed, key.borrow(), val.as_ref()), owned),
+                    Form::Owned => (borrowed, f(owned, key.borrow(), val.as_ref())),
+                }
+            })
+    }
+
+    /// Captures the form of every entry in the map, so that it can
+    /// later be compared against the map with `diff_forms`.
+    /// 
+    /// The keys are cloned into the snapshot, but the values are not.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// let snapshot = hm.form_snapshot();
+    /// assert_eq!(snapshot.len(), 1);
+    /// ```
+    pub fn form_snapshot(&self) -> FormSnapshot<K> {
+        let forms = self.inner
+            .iter()
+            .map(|(k, v)| (k.as_ref().to_owned(), Form::of(v)))
+            .collect();
+
+        FormSnapshot { forms }
+    }
+
+    /// Returns the keys of the entries whose form has changed since the
+    /// given snapshot was taken.
+    /// 
+    /// Entries which were removed from or inserted into the map after
+    /// the snapshot was taken are not reported. The order of the keys is
+    /// unspecified.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_borrowed("key2", &[4, 5, 6]);
+    /// 
+    /// let snapshot = hm.form_snapshot();
+    /// hm.get_mut(&"key1");
+    /// 
+    /// assert_eq!(hm.diff_forms(&snapshot), vec!["key1"]);
+    /// ```
+    pub fn diff_forms(&self, snapshot: &FormSnapshot<K>) -> Vec<&K> {
+        snapshot.forms
+            .iter()
+            .filter_map(|(key, form)| {
+                self.inner
+                    .get_key_value(key.borrow())
+                    .filter(|(_, v)| Form::of(v) != *form)
+                    .map(|(k, _)| k.borrow())
+            })
+            .collect()
+    }
+
+    /// Returns a rough estimate of the number of bytes used by the map.
+    /// 
+    /// This accounts for the allocated table slots and for the heap
+    /// data of owned keys and values. Borrowed keys and values are
+    /// not counted, since their data is owned elsewhere.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// let before = hm.memory_size_hint();
+    /// 
+    /// hm.make_owned(&"key1");
+    /// assert!(hm.memory_size_hint() > before);
+    /// ```
+    pub fn memory_size_hint(&self) -> usize 
+        where <K as ToOwned>::Owned: HeapSize,
+              <V as ToOwned>::Owned: HeapSize,
+    {
+        let table = self.inner.capacity() * core::mem::size_of::<(Cow<'a, K>, Cow<'a, V>)>();
+
+        let data: usize = self.inner
+            .iter()
+            .map(|(k, v)| {
+                let key = match k {
+                    Cow::Borrowed(_) => 0,
+                    Cow::Owned(k) => k.heap_size(),
+                };
+                
+                let val = match v {
+                    Cow::Borrowed(_) => 0,
+                    Cow::Owned(v) => v.heap_size(),
+                };
+
+                key + val
+            })
+            .sum();
+
+        table + data
+    }
+
+    /// Returns a cloned version of the map but with
+    /// the entries in borrowed form.
+    /// 
+    /// The returned map borrows from `self` only for as long as
+    /// it is in use, so the original map can still be read while
+    /// the clone exists, and mutated once it is dropped.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// 
+    /// let hm_clone = hm.borrow_fields();
+    /// assert_eq!(hm_clone.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn borrow_fields<'b>(&'b self) -> CowHashMap<'b, K, V, S> 
+        where S: Clone
+    {
+        let mut collection = HashMap::with_capacity_and_hasher(self.len(), self.inner.hasher().clone());
+        let entries = self.inner
+            .iter()
+            .map(|(k, v)| {
+                match (k, v) {
+                    (Cow::Owned(key), Cow::Owned(val)) => {
+                        (Cow::Borrowed((*key).borrow()), Cow::Borrowed((*val).borrow()))
+                    }
+
+                    (Cow::Borrowed(key), Cow::Owned(val)) => {
+                        (Cow::Borrowed(*key), Cow::Borrowed((*val).borrow()))
+                    }
+
+                    (Cow::Owned(key), Cow::Borrowed(val)) => {
+                        (Cow::Borrowed((*key).borrow()), Cow::Borrowed(*val))
+                    }
+
+                    (Cow::Borrowed(key), Cow::Borrowed(val)) => {
+                        (Cow::Borrowed(*key), Cow::Borrowed(*val))
+                    }
+                }
+                
+            });
+
+        collection.extend(entries);
+        CowHashMap { inner: collection }
+    }
+
+    /// Returns a shallow clone of the map in which every key and value
+    /// is borrowed.
+    /// 
+    /// Borrowed entries keep sharing the data they borrow, while owned
+    /// entries are borrowed from `self`, so no data is cloned. This is
+    /// equivalent to `borrow_fields`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// let hm_clone = hm.clone_borrowed();
+    /// assert_eq!(hm_clone.entry_form(&"key").unwrap(), Form::Borrowed);
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn clone_borrowed<'b>(&'b self) -> CowHashMap<'b, K, V, S> 
+        where S: Clone
+    {
+        self.borrow_fields()
+    }
+
+    /// Returns a clone of the map in which every key and value keeps
+    /// its form.
+    /// 
+    /// Borrowed entries are copied as borrows of the same data, with the
+    /// same lifetime as in the original map, while owned entries are
+    /// cloned. Unlike `clone`, this does not materialize borrowed entries,
+    /// which makes it cheap for maps that mostly borrow `'static` data.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<'static, str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let hm_clone = hm.clone_preserving_forms();
+    /// assert_eq!(hm_clone.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// assert_eq!(hm_clone.entry_form(&"key2").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn clone_preserving_forms(&self) -> CowHashMap<'a, K, V, S> 
+        where S: Clone
+    {
+        CowHashMap { inner: self.inner.clone() }
+    }
+
+    /// Returns a new map with the same keys, in which every value is
+    /// replaced by the owned output of `f`.
+    /// 
+    /// All of the entries of the returned map are in owned form, and the
+    /// map itself is left untouched.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let lens: CowHashMap<str, usize> = hm.map_values(|v| v.len());
+    /// assert_eq!(lens.get(&"key").unwrap(), &3);
+    /// ```
+    pub fn map_values<W, F>(&self, mut f: F) -> CowHashMap<'a, K, W, S> 
+        where W: ToOwned + ?Sized,
+              F: FnMut(&V) -> <W as ToOwned>::Owned,
+              S: Clone,
+    {
+        let mut hm = CowHashMap::with_capacity_and_hasher(self.len(), self.inner.hasher().clone());
+
+        for (key, val) in self.iter() {
+            hm.insert_owned(key.to_owned(), f(val));
+        }
+
+        hm
+    }
+
+    /// Consumes the map and returns a map containing the entries for which
+    /// `f` returns `Some`, replaced by the returned key and value.
+    /// 
+    /// Every entry is passed to `f` in owned form, cloning borrowed data,
+    /// so all of the entries of the returned map are owned. If `f` returns
+    /// the same key for several entries, the last one wins.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1]);
+    /// hm.insert_borrowed("key2", &[]);
+    /// 
+    /// let hm = hm.filter_map_into(|k, mut v| {
+    ///     if v.is_empty() {
+    ///         None
+    ///     } else {
+    ///         v.push(2);
+    ///         Some((k.to_uppercase(), v))
+    ///     }
+    /// });
+    /// 
+    /// assert_eq!(hm.len(), 1);
+    /// assert_eq!(hm.get(&"KEY1").unwrap(), &[1, 2]);
+    /// ```
+    pub fn filter_map_into<F>(mut self, mut f: F) -> Self 
+        where F: FnMut(<K as ToOwned>::Owned, <V as ToOwned>::Owned) -> Option<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)>
+    {
+        // Entries are collected first since rewritten keys may collide
+        // with entries that have not been visited yet.
+        let entries: Vec<_> = self.inner
+            .drain()
+            .filter_map(|(k, v)| f(k.into_owned(), v.into_owned()))
+            .collect();
+
+        for (key, val) in entries {
+            self.insert_owned(key, val);
+        }
+
+        self
+    }
+
+    /// Clears `dest` and fills it with owned clones of all of the
+    /// entries in the map.
+    /// 
+    /// Unlike `clone`, this reuses the allocation of `dest`, which makes
+    /// it suitable for pooling maps. The previous contents of `dest` are
+    /// dropped.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// let mut dest: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// dest.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// hm.clone_owned_into(&mut dest);
+    /// 
+    /// assert_eq!(dest, hm);
+    /// assert_eq!(dest.entry_form(&"key1").unwrap(), Form::Owned);
+    /// ```
+    pub fn clone_owned_into<T>(&self, dest: &mut CowHashMap<'_, K, V, T>) 
+        where T: BuildHasher
+    {
+        dest.clear();
+        dest.reserve(self.len());
+
+        for (key, val) in self.iter() {
+            dest.insert_owned(key.to_owned(), val.to_owned());
+        }
+    }
+
+    /// Returns a `std::collections::HashMap` containing owned
+    /// clones of all of the entries in the map.
+    /// 
+    /// This function is only available with the `std` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let std_hm: HashMap<String, Vec<u8>> = hm.to_std_hashmap();
+    /// assert_eq!(std_hm["key1"], vec![1, 2, 3]);
+    /// assert_eq!(std_hm["key2"], vec![4, 5, 6]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_std_hashmap(&self) -> std::collections::HashMap<<K as ToOwned>::Owned, <V as ToOwned>::Owned> 
+        where <K as ToOwned>::Owned: Hash + Eq
+    {
+        let mut map = std::collections::HashMap::with_capacity(self.len());
+
+        for (key, val) in self.iter() {
+            map.insert(key.to_owned(), val.to_owned());
+        }
+
+        map
+    }
+
+    /// Returns a `std::collections::HashMap` of references to the keys
+    /// and values of the map.
+    /// 
+    /// Nothing is cloned, and the form of the entries is not altered.
+    /// This function is only available with the `std` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let refs: HashMap<&str, &[u8]> = hm.to_ref_map();
+    /// assert_eq!(refs["key1"], &[1, 2, 3]);
+    /// assert_eq!(refs["key2"], &[4, 5, 6]);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn to_ref_map(&self) -> std::collections::HashMap<&K, &V> {
+        self.iter().collect()
+    }
+
+    /// Groups the entries of the map by the output of `f`, and returns
+    /// the number of entries in each group.
+    /// 
+    /// This does not alter the form of any of the entries. This function
+    /// is only available with the `std` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// hm.insert_owned("key3".to_owned(), vec![7]);
+    /// 
+    /// let counts = hm.count_by(|_, v| v.len());
+    /// assert_eq!(counts[&3], 2);
+    /// assert_eq!(counts[&1], 1);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn count_by<T, F>(&self, mut f: F) -> std::collections::HashMap<T, usize> 
+        where T: Hash + Eq,
+              F: FnMut(&K, &V) -> T,
+    {
+        let mut counts = std::collections::HashMap::new();
+
+        for (key, val) in self.iter() {
+            *counts.entry(f(key, val)).or_insert(0) += 1;
+        }
+
+        counts
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    /// 
+    /// This does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// # #[macro_use] extern crate hashcow; fn main() {
+    /// # use std::collections::HashSet;
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// for (key, val) in hm.iter() {
+    ///     // ...
+    /// }
+    /// # }
+    /// ```
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.inner.iter().map(|(k, v)| (k.borrow(), v.as_ref()))
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order, along
+    /// with the form in which each value is stored.
+    /// 
+    /// This does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// for (key, val, form) in hm.iter_forms() {
+    ///     assert_eq!(key, "key1");
+    ///     assert_eq!(val, &[1, 2, 3]);
+    ///     assert_eq!(form, Form::Borrowed);
+    /// }
+    /// ```
+    #[inline]
+    pub fn iter_forms(&self) -> impl Iterator<Item = (&K, &V, Form)> {
+        self.inner.iter().map(|(k, v)| (k.borrow(), v.as_ref(), Form::of(v)))
+    }
+
+    /// An iterator visiting all key-value pairs in ascending key order.
+    /// 
+    /// The entries are collected and sorted up front, so this takes
+    /// O(n log n) time and O(n) additional memory. This does not alter
+    /// the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key2", &[4, 5, 6]);
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// let keys: Vec<&str> = hm.sorted_iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec!["key1", "key2"]);
+    /// ```
+    pub fn sorted_iter(&self) -> impl Iterator<Item = (&K, &V)> 
+        where K: Ord
+    {
+        let mut entries: Vec<(&K, &V)> = self.iter().collect();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+        entries.into_iter()
+    }
+
+    /// An iterator visiting the key-value pairs whose keys fall within
+    /// the specified range, in ascending key order.
+    /// 
+    /// The matching entries are collected and sorted up front, so this
+    /// takes O(n log n) time in the worst case. For unsized keys such as
+    /// `str`, the range can be given as a pair of `Bound`s. This does not
+    /// alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use std::ops::Bound;
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("a", &[1]);
+    /// hm.insert_owned("b".to_owned(), vec![2]);
+    /// hm.insert_owned("c".to_owned(), vec![3]);
+    /// 
+    /// let keys: Vec<&str> = hm.range((Bound::Included("a"), Bound::Excluded("c")))
+    ///     .map(|(k, _)| k)
+    ///     .collect();
+    /// 
+    /// assert_eq!(keys, vec!["a", "b"]);
+    /// ```
+    pub fn range<R>(&self, range: R) -> impl Iterator<Item = (&K, &V)> 
+        where K: Ord,
+              R: RangeBounds<K>,
+    {
+        let mut entries: Vec<(&K, &V)> = self.iter().filter(|(k, _)| range.contains(*k)).collect();
+        entries.sort_unstable_by_key(|(k, _)| *k);
+        entries.into_iter()
+    }
+
+    /// Returns the entry with the smallest key in the map.
+    /// 
+    /// This scans the map once without sorting it, and does not alter
+    /// the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key2", &[4, 5, 6]);
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.min_key_value(), Some(("key1", &[1, 2, 3][..])));
+    /// ```
+    #[inline]
+    pub fn min_key_value(&self) -> Option<(&K, &V)> 
+        where K: Ord
+    {
+        self.iter().min_by_key(|(k, _)| *k)
+    }
+
+    /// Returns the entry with the largest key in the map.
+    /// 
+    /// This scans the map once without sorting it, and does not alter
+    /// the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key2", &[4, 5, 6]);
+    /// hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm.max_key_value(), Some(("key2", &[4, 5, 6][..])));
+    /// ```
+    #[inline]
+    pub fn max_key_value(&self) -> Option<(&K, &V)> 
+        where K: Ord
+    {
+        self.iter().max_by_key(|(k, _)| *k)
+    }
+
+    /// An iterator visiting all key-value pairs whose value is stored in
+    /// borrowed form, in arbitrary order.
+    /// 
+    /// This does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let entries: Vec<(&str, &[u8])> = hm.borrowed_entries().collect();
+    /// assert_eq!(entries, vec![("key1", &[1, 2, 3][..])]);
+    /// ```
+    #[inline]
+    pub fn borrowed_entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.inner
+            .iter()
+            .filter(|(_, v)| Form::of(v) == Form::Borrowed)
+            .map(|(k, v)| (k.borrow(), v.as_ref()))
+    }
+
+    /// An iterator visiting all key-value pairs whose value is stored in
+    /// owned form, in arbitrary order.
+    /// 
+    /// This does not alter the form of any of the entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let entries: Vec<(&str, &[u8])> = hm.owned_entries().collect();
+    /// assert_eq!(entries, vec![("key2", &[4, 5, 6][..])]);
+    /// ```
+    #[inline]
+    pub fn owned_entries(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.inner
+            .iter()
+            .filter(|(_, v)| Form::of(v) == Form::Owned)
+            .map(|(k, v)| (k.borrow(), v.as_ref()))
+    }
+
+    /// Removes a key from the map, returning the value at the key if the key was previously in the map.
+    /// 
+    /// If the removed value is borrowed, it will be cloned and then returned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// assert!(hm.remove(&"key1").is_none());
+    /// 
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// assert_eq!(hm.remove(&"key1").unwrap(), vec![1, 2, 3]);
+    /// ```
+    #[inline]
+    pub fn remove(&mut self, key: &K) -> Option<<V as ToOwned>::Owned> {
+        self.inner.remove(key).map(|x| x.into_owned())
+    }
+
+    /// Removes a key from the map, returning the stored key and value if the key was previously in the map.
+    /// 
+    /// Both the key and the value are returned in owned form. If either of them
+    /// is borrowed, it will be cloned and then returned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// assert!(hm.remove_entry(&"key1").is_none());
+    /// 
+    /// hm.insert_owned_borrowed_key("key1", vec![1, 2, 3]);
+    /// assert_eq!(hm.remove_entry(&"key1").unwrap(), ("key1".to_owned(), vec![1, 2, 3]));
+    /// ```
+    #[inline]
+    pub fn remove_entry(&mut self, key: &K) -> Option<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)> {
+        self.inner.remove_entry(key).map(|(k, v)| (k.into_owned(), v.into_owned()))
+    }
+
+    /// Removes all of the given keys from the map, and returns the
+    /// number of entries that were actually removed.
+    /// 
+    /// Keys which are not present in the map are skipped.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1]);
+    /// hm.insert_borrowed("key2", &[2]);
+    /// 
+    /// assert_eq!(hm.remove_all(&["key1", "key3"]), 1);
+    /// assert_eq!(hm.len(), 1);
+    /// ```
+    #[inline]
+    pub fn remove_all(&mut self, keys: &[&K]) -> usize {
+        keys.iter()
+            .filter(|key| self.inner.remove(**key).is_some())
+            .count()
+    }
+
+    /// Moves the value stored at `old` to the owned key `new`, returning
+    /// `false` if `old` is not present in the map.
+    /// 
+    /// The value keeps its form, so a borrowed value stays borrowed. Any
+    /// value previously stored at `new` is dropped.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// 
+    /// assert!(hm.replace_key(&"key1", "key2".to_owned()));
+    /// assert!(!hm.contains_key(&"key1"));
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn replace_key(&mut self, old: &K, new: <K as ToOwned>::Owned) -> bool {
+        match self.inner.remove(old) {
+            Some(val) => {
+                self.inner.insert(Cow::Owned(new), val);
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    /// Swaps the values stored at the keys `a` and `b`, returning `false`
+    /// if either key is not present in the map.
+    /// 
+    /// No data is cloned, and each value keeps its form as it moves to
+    /// the other key.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert!(hm.swap_values(&"key1", &"key2"));
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[4, 5, 6]);
+    /// assert_eq!(hm.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn swap_values(&mut self, a: &K, b: &K) -> bool {
+        if a == b {
+            return self.inner.contains_key(a);
+        }
+
+        match self.inner.get_many_mut([a, b]) {
+            Some([val_a, val_b]) => {
+                core::mem::swap(val_a, val_b);
+                true
+            }
+
+            None => false,
+        }
+    }
+
+    /// Clears the map, returning all key-value pairs as an iterator. Keeps
+    /// the allocated memory for reuse.
+    /// 
+    /// Borrowed keys and values are cloned so that every yielded pair is owned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let pairs: Vec<(String, Vec<u8>)> = hm.drain().collect();
+    /// 
+    /// assert_eq!(pairs, vec![("key".to_owned(), vec![1, 2, 3])]);
+    /// assert!(hm.is_empty());
+    /// ```
+    #[inline]
+    pub fn drain(&mut self) -> Drain<'_, 'a, K, V> {
+        Drain { inner: self.inner.drain() }
+    }
+
+    /// Moves all of the entries of `other` into the map.
+    /// 
+    /// If a key from `other` is already present in the map, `resolve` is
+    /// called with the key, a mutable reference to the owned form of the
+    /// existing value and the incoming value in owned form. Otherwise,
+    /// the entry is moved into the map preserving its form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm1.insert_borrowed("key1", &[1]);
+    /// 
+    /// let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm2.insert_owned("key1".to_owned(), vec![2]);
+    /// hm2.insert_borrowed("key2", &[3]);
+    /// 
+    /// hm1.merge(hm2, |_, existing, incoming| existing.extend(incoming));
+    /// 
+    /// assert_eq!(hm1.get(&"key1").unwrap(), &[1, 2]);
+    /// assert_eq!(hm1.get(&"key2").unwrap(), &[3]);
+    /// assert_eq!(hm1.entry_form(&"key2").unwrap(), Form::Borrowed);
+    /// ```
+    pub fn merge<T, F>(&mut self, other: CowHashMap<'a, K, V, T>, mut resolve: F) 
+        where F: FnMut(&K, &mut <V as ToOwned>::Owned, <V as ToOwned>::Owned)
+    {
+        self.reserve(other.inner.len());
+
+        for (key, val) in other.inner {
+            match self.inner.get_mut(key.as_ref()) {
+                Some(existing) => resolve(key.borrow(), existing.to_mut(), val.into_owned()),
+                None => {
+                    self.inner.insert(key, val);
+                }
+            }
+        }
+    }
+
+    /// Splits the map into two maps according to the form of the
+    /// values, returning `(borrowed_entries, owned_entries)`.
+    /// 
+    /// Keys are moved into the new maps as they are, so their form
+    /// is preserved.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let (borrowed, owned) = hm.split_by_form();
+    /// 
+    /// assert!(borrowed.contains_key(&"key1"));
+    /// assert!(owned.contains_key(&"key2"));
+    /// ```
+    pub fn split_by_form(mut self) -> (Self, Self) 
+        where S: Clone
+    {
+        let mut borrowed = HashMap::with_hasher(self.inner.hasher().clone());
+        borrowed.extend(self.inner.extract_if(|_, v| Form::of(v) == Form::Borrowed));
+
+        (CowHashMap { inner: borrowed }, self)
+    }
+
+    /// Splits the map into two maps according to the predicate, returning
+    /// `(matched, unmatched)`.
+    /// 
+    /// Entries are moved into the new maps as they are, so both their keys
+    /// and values keep their form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4]);
+    /// 
+    /// let (long, short) = hm.partition(|_, v| v.len() > 1);
+    /// 
+    /// assert!(long.contains_key(&"key1"));
+    /// assert!(short.contains_key(&"key2"));
+    /// ```
+    pub fn partition<F>(mut self, mut f: F) -> (Self, Self) 
+        where F: FnMut(&K, &V) -> bool,
+              S: Clone,
+    {
+        let mut matched = HashMap::with_hasher(self.inner.hasher().clone());
+        matched.extend(self.inner.extract_if(|k, v| f(k.borrow(), v.as_ref())));
+
+        (CowHashMap { inner: matched }, self)
+    }
+
+    /// Computes the difference between the map and `other`.
+    /// 
+    /// The returned `MapDiff` lists the entries that are only present in
+    /// `other`, the keys that are only present in the map and the entries
+    /// whose values differ between the two maps. Values are compared by
+    /// their dereferenced contents, so the form of an entry is ignored.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm1.insert_borrowed("key1", &[1]);
+    /// hm1.insert_borrowed("key2", &[2]);
+    /// 
+    /// let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm2.insert_owned("key1".to_owned(), vec![1]);
+    /// hm2.insert_owned("key3".to_owned(), vec![3]);
+    /// 
+    /// let diff = hm1.diff(&hm2);
+    /// 
+    /// assert_eq!(diff.added, vec![("key3".to_owned(), vec![3])]);
+    /// assert_eq!(diff.removed, vec!["key2".to_owned()]);
+    /// assert!(diff.changed.is_empty());
+    /// ```
+    pub fn diff<T>(&self, other: &CowHashMap<'_, K, V, T>) -> MapDiff<K, V> 
+        where V: PartialEq,
+              T: BuildHasher,
+    {
+        let mut diff = MapDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+
+        for (key, val) in self.inner.iter() {
+            match other.inner.get(key.as_ref()) {
+                Some(other_val) if other_val.as_ref() != val.as_ref() => {
+                    diff.changed.push((key.as_ref().to_owned(), other_val.as_ref().to_owned()));
+                }
+
+                Some(_) => {}
+                None => diff.removed.push(key.as_ref().to_owned()),
+            }
+        }
+
+        for (key, val) in other.inner.iter() {
+            if !self.inner.contains_key(key.as_ref()) {
+                diff.added.push((key.as_ref().to_owned(), val.as_ref().to_owned()));
+            }
+        }
+
+        diff
+    }
+
+    /// Applies a `MapDiff` to the map.
+    /// 
+    /// The removed keys are taken out of the map, while the added and
+    /// changed entries are inserted in owned form, overwriting any existing
+    /// values. Removed keys which are not present in the map are ignored.
+    /// 
+    /// Applying the diff of the map against another map makes the map
+    /// equal to the other map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm1.insert_borrowed("key1", &[1]);
+    /// hm1.insert_borrowed("key2", &[2]);
+    /// 
+    /// let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm2.insert_owned("key1".to_owned(), vec![4]);
+    /// hm2.insert_owned("key3".to_owned(), vec![3]);
+    /// 
+    /// let diff = hm1.diff(&hm2);
+    /// hm1.apply_patch(&diff);
+    /// 
+    /// assert_eq!(hm1, hm2);
+    /// ```
+    pub fn apply_patch(&mut self, patch: &MapDiff<K, V>) {
+        for key in patch.removed.iter() {
+            self.inner.remove(key.borrow());
+        }
+
+        self.reserve(patch.added.len());
+
+        for (key, val) in patch.added.iter().chain(patch.changed.iter()) {
+            self.insert_owned(key.borrow().to_owned(), val.borrow().to_owned());
+        }
+    }
+
+    /// Removes the entries for which the predicate returns `true`, and
+    /// returns an iterator over the removed entries in owned form.
+    /// 
+    /// Borrowed data of the removed entries is cloned. Entries for which
+    /// the predicate returns `false` stay in the map and keep their form.
+    /// If the returned iterator is not exhausted, the remaining matching
+    /// entries are kept in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4]);
+    /// 
+    /// let extracted: Vec<(String, Vec<u8>)> = hm.extract_if(|_, v| v.len() > 1).collect();
+    /// 
+    /// assert_eq!(extracted, vec![("key1".to_owned(), vec![1, 2, 3])]);
+    /// assert_eq!(hm.len(), 1);
+    /// ```
+    #[inline]
+    pub fn extract_if<F>(&mut self, mut f: F) -> ExtractIf<'_, 'a, K, V, impl FnMut(&Cow<'a, K>, &mut Cow<'a, V>) -> bool> 
+        where F: FnMut(&K, &V) -> bool
+    {
+        ExtractIf { inner: self.inner.extract_if(move |k, v| f(k.borrow(), v.as_ref())) }
+    }
+
+    /// Removes the entries whose value is stored in owned form, and
+    /// returns an iterator over the removed entries.
+    /// 
+    /// Borrowed entries stay in the map and keep their form. Since only
+    /// the form of the values is taken into account, a borrowed key of
+    /// a removed entry is cloned. If the returned iterator is not
+    /// exhausted, the remaining owned entries are kept in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let drained: Vec<(String, Vec<u8>)> = hm.drain_owned().collect();
+    /// 
+    /// assert_eq!(drained, vec![("key2".to_owned(), vec![4, 5, 6])]);
+    /// assert!(hm.contains_key(&"key1"));
+    /// ```
+    #[inline]
+    pub fn drain_owned(&mut self) -> ExtractIf<'_, 'a, K, V, impl FnMut(&Cow<'a, K>, &mut Cow<'a, V>) -> bool> {
+        ExtractIf { inner: self.inner.extract_if(|_, v| Form::of(v) == Form::Owned) }
+    }
+
+    /// Retains only the entries specified by the predicate.
+    /// 
+    /// The predicate is given references to the key and value of each
+    /// entry, so inspecting an entry does not clone it. Retained entries
+    /// keep their original form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4]);
+    /// 
+    /// hm.retain(|_, v| v.len() > 1);
+    /// 
+    /// assert_eq!(hm.len(), 1);
+    /// assert_eq!(hm.entry_form(&"key1").unwrap(), Form::Borrowed);
+    /// ```
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F) 
+        where F: FnMut(&K, &V) -> bool
+    {
+        self.inner.retain(|k, v| f(k.borrow(), v.as_ref()));
+    }
+
+    /// Retains only the entries specified by the predicate, which is
+    /// also given the form of each entry's value.
+    /// 
+    /// As with `retain`, inspecting an entry does not clone it and
+    /// retained entries keep their original form.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// hm.retain_with_form(|_, _, form| form == Form::Owned);
+    /// 
+    /// assert_eq!(hm.len(), 1);
+    /// assert!(hm.contains_key(&"key2"));
+    /// ```
+    #[inline]
+    pub fn retain_with_form<F>(&mut self, mut f: F) 
+        where F: FnMut(&K, &V, Form) -> bool
+    {
+        self.inner.retain(|k, v| f(k.borrow(), v.as_ref(), Form::of(v)));
+    }
+
+    /// Removes every entry whose value is stored in borrowed form, and
+    /// returns the number of removed entries.
+    /// 
+    /// Owned entries are left untouched, so after calling this function
+    /// the map no longer borrows any values.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// assert_eq!(hm.evict_borrowed(), 1);
+    /// assert!(hm.contains_key(&"key2"));
+    /// ```
+    #[inline]
+    pub fn evict_borrowed(&mut self) -> usize {
+        let len = self.inner.len();
+        self.inner.retain(|_, v| Form::of(v) == Form::Owned);
+        len - self.inner.len()
+    }
+
+    /// Repeatedly removes the entry whose key is returned by `choose`
+    /// until the length of the map is at most `target_len`, and returns
+    /// the number of removed entries.
+    /// 
+    /// `choose` is given the map as it currently is, so it can pick the
+    /// next victim based on the remaining entries. Eviction stops early if
+    /// `choose` returns `None` or a key which is not present in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_owned("key1".to_owned(), vec![1]);
+    /// hm.insert_owned("key2".to_owned(), vec![2]);
+    /// hm.insert_owned("key3".to_owned(), vec![3]);
+    /// 
+    /// let evicted = hm.evict_while_over(1, |hm| hm.min_key_value().map(|(k, _)| k.to_owned()));
+    /// 
+    /// assert_eq!(evicted, 2);
+    /// assert!(hm.contains_key(&"key3"));
+    /// ```
+    pub fn evict_while_over<F>(&mut self, target_len: usize, mut choose: F) -> usize 
+        where F: FnMut(&Self) -> Option<<K as ToOwned>::Owned>
+    {
+        let mut evicted = 0;
+
+        while self.len() > target_len {
+            match choose(self) {
+                Some(key) if self.inner.remove(key.borrow()).is_some() => evicted += 1,
+                _ => break,
+            }
+        }
+
+        evicted
+    }
+}
+
+impl<'a, K, V, S> Default for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher + Default,
+{
+    /// Creates an empty `CowHashMap` with the default hasher.
+    #[inline]
+    fn default() -> Self {
+        CowHashMap::with_hasher(S::default())
+    }
+}
+
+impl<'a, K, V, S> Clone for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher + Clone,
+{
+    /// Returns a deep clone of the map with all of the entries in owned form.
+    /// 
+    /// The clone does not borrow any data from the original map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let hm_clone = hm.clone();
+    /// assert_eq!(hm_clone.entry_form(&"key").unwrap(), Form::Owned);
+    /// assert_eq!(hm_clone.get(&"key").unwrap(), &[1, 2, 3]);
+    /// ```
+    #[inline]
+    fn clone(&self) -> Self {
+        let mut collection = HashMap::with_capacity_and_hasher(self.len(), self.inner.hasher().clone());
+        let entries = self.inner
+            .iter()
+            .map(|(k, v)| (Cow::Owned(k.as_ref().to_owned()), Cow::Owned(v.as_ref().to_owned())));
+
+        collection.extend(entries);
+        CowHashMap { inner: collection }
+    }
+}
+
+impl<'a, K, V, S> PartialEq for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized + PartialEq,
+          S: BuildHasher,
+{
+    /// Two maps are equal if they contain the same keys mapped to
+    /// equal values, regardless of the form of their entries.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm1.insert_borrowed("key", &[1, 2, 3]);
+    /// hm2.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm1, hm2);
+    /// ```
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner == other.inner
+    }
+}
+
+impl<'a, K, V, S> Eq for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized + Eq,
+          S: BuildHasher,
+{ }
+
+impl<'a, 'q, K, V, S> Index<&'q K> for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    type Output = V;
+
+    /// Returns a reference to the value corresponding to the supplied key.
+    /// 
+    /// This does not alter the form of the entry.
+    /// 
+    /// ## Panics
+    /// Panics if the key is not present in the map.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// assert_eq!(&hm["key"], &[1, 2, 3]);
+    /// ```
+    #[inline]
+    fn index(&self, key: &K) -> &V {
+        self.get(key).expect("no entry found for key")
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V, S, T> PartialEq<std::collections::HashMap<<K as ToOwned>::Owned, <V as ToOwned>::Owned, T>> for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized + PartialEq,
+          <K as ToOwned>::Owned: Hash + Eq,
+          S: BuildHasher,
+          T: BuildHasher,
+{
+    /// A map is equal to a `std::collections::HashMap` if they contain
+    /// the same keys mapped to equal values, regardless of the form of
+    /// the entries of the map.
+    /// 
+    /// This is only available with the `std` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use std::collections::HashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let mut expected: HashMap<String, Vec<u8>> = HashMap::new();
+    /// expected.insert("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// assert_eq!(hm, expected);
+    /// ```
+    fn eq(&self, other: &std::collections::HashMap<<K as ToOwned>::Owned, <V as ToOwned>::Owned, T>) -> bool {
+        self.len() == other.len() && self.iter().all(|(key, val)| {
+            other
+                .get(key)
+                .is_some_and(|other_val| other_val.borrow() == val)
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, K, V, S> Hash for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized + Hash,
+{
+    /// Hashes the map independently of the iteration order and of
+    /// the form of its entries, so that maps which are equal also
+    /// hash equally.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use std::collections::HashSet;
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm1.insert_borrowed("key", &[1, 2, 3]);
+    /// hm2.insert_owned("key".to_owned(), vec![1, 2, 3]);
+    /// 
+    /// let mut set = HashSet::new();
+    /// set.insert(hm1);
+    /// 
+    /// assert!(set.contains(&hm2));
+    /// ```
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        use std::collections::hash_map::DefaultHasher;
+        use core::hash::Hasher;
+
+        let sum = self.inner
+            .iter()
+            .map(|(k, v)| {
+                let mut hasher = DefaultHasher::new();
+                k.hash(&mut hasher);
+                v.hash(&mut hasher);
+                hasher.finish()
+            })
+            .fold(0u64, u64::wrapping_add);
+
+        state.write_usize(self.inner.len());
+        state.write_u64(sum);
+    }
+}
+
+impl<'a, K, V, S> fmt::Debug for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned + fmt::Debug,
+          V: ToOwned + ?Sized + fmt::Debug,
+          S: BuildHasher,
+{
+    /// Formats the map in the same manner as a `HashMap`. The form
+    /// of the entries is not shown.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a, K, V, S> FromIterator<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)> for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher + Default,
+{
+    /// Creates a map from an iterator of owned key/value pairs. All
+    /// of the entries are stored in owned form.
+    /// 
+    /// If a key is yielded more than once, the last value is kept.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let hm: CowHashMap<str, [u8]> = vec![("key".to_owned(), vec![1, 2, 3])]
+    ///     .into_iter()
+    ///     .collect();
+    /// 
+    /// assert_eq!(hm.get(&"key").unwrap(), &[1, 2, 3]);
+    /// ```
+    fn from_iter<I>(iter: I) -> Self 
+        where I: IntoIterator<Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned)>
+    {
+        let mut map = CowHashMap::with_hasher(S::default());
+        map.extend(iter);
+        map
+    }
+}
+
+impl<'a, K, V, S> Extend<(<K as ToOwned>::Owned, <V as ToOwned>::Owned)> for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Inserts all of the owned key/value pairs yielded by the iterator,
+    /// in the same manner as `insert_owned`.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.extend(vec![("key1".to_owned(), vec![4]), ("key2".to_owned(), vec![5])]);
+    /// 
+    /// assert_eq!(hm.get(&"key1").unwrap(), &[4]);
+    /// assert_eq!(hm.get(&"key2").unwrap(), &[5]);
+    /// ```
+    fn extend<I>(&mut self, iter: I) 
+        where I: IntoIterator<Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned)>
+    {
+        let iter = iter.into_iter();
+        self.reserve(iter.size_hint().0);
+
+        for (key, val) in iter {
+            self.insert_owned(key, val);
+        }
+    }
+}
+
+impl<'a, K, V, S> IntoIterator for CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    type Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned);
+    type IntoIter = IntoIter<'a, K, V>;
+
+    /// Creates a consuming iterator visiting all key-value pairs in arbitrary order.
+    /// 
+    /// Borrowed keys and values are cloned so that every yielded pair is owned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key", &[1, 2, 3]);
+    /// 
+    /// let pairs: Vec<(String, Vec<u8>)> = hm.into_iter().collect();
+    /// assert_eq!(pairs, vec![("key".to_owned(), vec![1, 2, 3])]);
+    /// ```
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        IntoIter { inner: self.inner.into_iter() }
+    }
+}
+
+/// A consuming iterator over the entries of a `CowHashMap`, yielding
+/// keys and values in owned form.
+pub struct IntoIter<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    inner: hashbrown::hash_map::IntoIter<Cow<'a, K>, Cow<'a, V>>
+}
+
+impl<'a, K, V> Iterator for IntoIter<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    type Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k.into_owned(), v.into_owned()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A consuming iterator over the keys of a `CowHashMap`, yielding
+/// the keys in owned form.
+pub struct IntoKeys<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    inner: hashbrown::hash_map::IntoKeys<Cow<'a, K>, Cow<'a, V>>
+}
+
+impl<'a, K, V> Iterator for IntoKeys<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    type Item = <K as ToOwned>::Owned;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|k| k.into_owned())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A consuming iterator over the values of a `CowHashMap`, yielding
+/// the values in owned form.
+pub struct IntoValues<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    inner: hashbrown::hash_map::IntoValues<Cow<'a, K>, Cow<'a, V>>
+}
+
+impl<'a, K, V> Iterator for IntoValues<'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    type Item = <V as ToOwned>::Owned;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|v| v.into_owned())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A mutable iterator over the values of a `CowHashMap`, yielding
+/// the values in owned form.
+pub struct ValuesMut<'b, 'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    inner: hashbrown::hash_map::ValuesMut<'b, Cow<'a, K>, Cow<'a, V>>
+}
+
+impl<'b, 'a, K, V> Iterator for ValuesMut<'b, 'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    type Item = &'b mut <V as ToOwned>::Owned;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|v| v.to_mut())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// A draining iterator over the entries of a `CowHashMap`, yielding
+/// keys and values in owned form.
+pub struct Drain<'b, 'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    inner: hashbrown::hash_map::Drain<'b, Cow<'a, K>, Cow<'a, V>>
+}
+
+impl<'b, 'a, K, V> Iterator for Drain<'b, 'a, K, V> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+{
+    type Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k.into_owned(), v.into_owned()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// An iterator over the entries removed from a `CowHashMap` by
+/// `extract_if` or `drain_owned`, yielding keys and values in owned form.
+pub struct ExtractIf<'b, 'a, K, V, F> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+          F: FnMut(&Cow<'a, K>, &mut Cow<'a, V>) -> bool,
+{
+    inner: hashbrown::hash_map::ExtractIf<'b, Cow<'a, K>, Cow<'a, V>, F>
+}
+
+impl<'b, 'a, K, V, F> Iterator for ExtractIf<'b, 'a, K, V, F> 
+    where K: ToOwned + ?Sized,
+          V: ToOwned + ?Sized,
+          F: FnMut(&Cow<'a, K>, &mut Cow<'a, V>) -> bool,
+{
+    type Item = (<K as ToOwned>::Owned, <V as ToOwned>::Owned);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k.into_owned(), v.into_owned()))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+#[macro_use]
+mod macros;
+
+#[cfg(all(test, feature = "std"))]
 mod tests {
     use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn remove_borrowed_entry() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key", &[1, 2, 3]);
+
+        assert_eq!(hm.remove("key"), Some(vec![1, 2, 3]));
+        assert!(hm.is_empty());
+    }
+
+    #[test]
+    fn remove_owned_entry() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+
+        assert_eq!(hm.remove("key"), Some(vec![1, 2, 3]));
+        assert!(hm.is_empty());
+    }
+
+    #[test]
+    fn remove_absent_key() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+
+        assert!(hm.remove("other").is_none());
+        assert_eq!(hm.len(), 1);
+    }
+
+    #[test]
+    fn remove_entry_mixed_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_owned_borrowed_key("key2", vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        assert_eq!(hm.remove_entry("key1"), Some(("key1".to_owned(), vec![1])));
+        assert_eq!(hm.remove_entry("key2"), Some(("key2".to_owned(), vec![2])));
+        assert_eq!(hm.remove_entry("key3"), Some(("key3".to_owned(), vec![3])));
+        assert_eq!(hm.remove_entry("key4"), Some(("key4".to_owned(), vec![4])));
+        assert!(hm.remove_entry("key1").is_none());
+        assert!(hm.is_empty());
+    }
+
+    #[test]
+    fn contains_key_preserves_form() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        assert!(hm.contains_key("key1"));
+        assert!(hm.contains_key("key2"));
+        assert!(!hm.contains_key("key3"));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn clear_retains_capacity() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_borrowed("key2", &[2]);
+        hm.insert_owned_borrowed_key("key3", vec![3]);
+
+        let capacity = hm.capacity();
+        hm.clear();
+
+        assert!(hm.is_empty());
+        assert_eq!(hm.len(), 0);
+        assert_eq!(hm.capacity(), capacity);
+    }
+
+    #[test]
+    fn into_iter_yields_owned_pairs() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_borrowed("key2", &[2]);
+        hm.insert_owned_borrowed_key("key3", vec![3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        let mut pairs: Vec<(String, Vec<u8>)> = hm.into_iter().collect();
+        pairs.sort();
+
+        assert_eq!(pairs, vec![
+            ("key1".to_owned(), vec![1]),
+            ("key2".to_owned(), vec![2]),
+            ("key3".to_owned(), vec![3]),
+            ("key4".to_owned(), vec![4]),
+        ]);
+    }
+
+    #[test]
+    fn iter_visits_all_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let entries: std::collections::HashMap<&str, &[u8]> = hm.iter().collect();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries["key1"], &[1, 2, 3]);
+        assert_eq!(entries["key2"], &[4, 5, 6]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn values_visits_all_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let values: HashSet<&[u8]> = hm.values().collect();
+        let expected: HashSet<&[u8]> = set![&[1, 2, 3][..], &[4, 5, 6][..]];
+
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn values_mut_materializes_borrowed() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        for val in hm.values_mut() {
+            val.push(0);
+        }
+
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3, 0]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6, 0]);
+    }
+
+    #[test]
+    fn entry_occupied_borrowed() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key", &[1, 2, 3]);
+
+        let val = hm.entry("key".to_owned()).or_insert_borrowed(&[4]);
+        assert_eq!(val, &[1, 2, 3]);
+        assert_eq!(hm.entry_form("key"), Some(Form::Borrowed));
+
+        hm.entry("key".to_owned()).and_modify(|v| v.push(4));
+        assert_eq!(hm.get("key").unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(hm.entry_form("key"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn entry_occupied_owned() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key".to_owned(), vec![1, 2, 3]);
+
+        hm.entry("key".to_owned()).or_insert_owned(vec![4]).push(5);
+        assert_eq!(hm.get("key").unwrap(), &[1, 2, 3, 5]);
+        assert_eq!(hm.entry_form("key"), Some(Form::Owned));
+        assert_eq!(hm.len(), 1);
+    }
+
+    #[test]
+    fn entry_vacant() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        hm.entry("key1".to_owned()).and_modify(|v| v.push(0));
+        assert!(hm.is_empty());
+
+        hm.entry("key1".to_owned()).or_insert_owned(vec![1]);
+        hm.entry("key2".to_owned()).or_insert_borrowed(&[2]);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1]);
+        assert_eq!(hm.get("key2").unwrap(), &[2]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn get_key_value_returns_stored_key() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+        hm.insert_borrowed("key2", &[4, 5, 6]);
+
+        let stored: *const str = hm.keys().find(|k| *k == "key1").unwrap();
+        let lookup = String::from("key1");
+        let (key, val) = hm.get_key_value(lookup.as_str()).unwrap();
+
+        assert_eq!(key, "key1");
+        assert_eq!(val, &[1, 2, 3]);
+        assert!(std::ptr::eq(key, stored));
+        assert!(!std::ptr::eq(key, lookup.as_str()));
+
+        let (key, val) = hm.get_key_value("key2").unwrap();
+        assert_eq!(key, "key2");
+        assert_eq!(val, &[4, 5, 6]);
+    }
+
+    #[test]
+    fn clone_is_owned_and_independent() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let mut hm_clone = hm.clone();
+        assert_eq!(hm_clone.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm_clone.entry_form("key2"), Some(Form::Owned));
+
+        hm_clone.get_mut("key1").unwrap().push(0);
+        hm_clone.get_mut("key2").unwrap().push(0);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm_clone.get("key1").unwrap(), &[1, 2, 3, 0]);
+        assert_eq!(hm_clone.get("key2").unwrap(), &[4, 5, 6, 0]);
+    }
+
+    #[test]
+    fn debug_hides_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let formatted = format!("{:?}", hm);
+
+        assert!(formatted.starts_with('{') && formatted.ends_with('}'));
+        assert!(formatted.contains("\"key1\": [1, 2, 3]"));
+        assert!(formatted.contains("\"key2\": [4, 5, 6]"));
+        assert!(!formatted.contains("Borrowed"));
+        assert!(!formatted.contains("Owned"));
+    }
+
+    #[test]
+    fn eq_ignores_forms() {
+        let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm1.insert_owned("key1".to_owned(), vec![1]);
+        hm1.insert_borrowed("key2", &[2]);
+        hm1.insert_owned_borrowed_key("key3", vec![3]);
+
+        let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm2.insert_borrowed("key1", &[1]);
+        hm2.insert_borrowed_owned_key("key2".to_owned(), &[2]);
+        hm2.insert_owned("key3".to_owned(), vec![3]);
+
+        assert_eq!(hm1, hm2);
+
+        hm2.get_mut("key3").unwrap().push(4);
+        assert_ne!(hm1, hm2);
+
+        hm2.remove("key3");
+        assert_ne!(hm1, hm2);
+    }
+
+    #[test]
+    fn custom_hasher() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::BuildHasherDefault;
+
+        let hasher = BuildHasherDefault::<DefaultHasher>::default();
+        let mut hm: CowHashMap<str, [u8], _> = CowHashMap::with_capacity_and_hasher(2, hasher);
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+        assert!(hm.get("key3").is_none());
+
+        let hm_clone = hm.clone();
+        assert_eq!(hm_clone, hm);
+        assert_eq!(hm.borrow_fields().entry_form("key2"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn make_all_owned_materializes_everything() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_owned_borrowed_key("key2", vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        hm.make_all_owned();
+
+        assert_eq!(hm.len(), 4);
+        for (key, val) in [("key1", [1]), ("key2", [2]), ("key3", [3]), ("key4", [4])].iter() {
+            assert_eq!(hm.entry_form(key), Some(Form::Owned));
+            assert_eq!(hm.get(key).unwrap(), val);
+        }
+
+        assert!(hm.inner.keys().all(|k| match k {
+            Cow::Owned(_) => true,
+            Cow::Borrowed(_) => false,
+        }));
+    }
+
+    #[test]
+    fn form_counts() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        assert_eq!(hm.owned_count(), 0);
+        assert_eq!(hm.borrowed_count(), 0);
+
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_owned_borrowed_key("key2", vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        assert_eq!(hm.owned_count(), 2);
+        assert_eq!(hm.borrowed_count(), 2);
+
+        hm.get_mut("key3").unwrap();
+
+        assert_eq!(hm.owned_count(), 3);
+        assert_eq!(hm.borrowed_count(), 1);
+        assert_eq!(hm.owned_count() + hm.borrowed_count(), hm.len());
+    }
+
+    #[test]
+    fn to_std_hashmap_clones_all_entries() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let std_hm: std::collections::HashMap<String, Vec<u8>> = hm.to_std_hashmap();
+
+        assert_eq!(std_hm.len(), 2);
+        assert_eq!(std_hm["key1"], vec![1, 2, 3]);
+        assert_eq!(std_hm["key2"], vec![4, 5, 6]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn from_iter_last_wins() {
+        let hm: CowHashMap<str, [u8]> = vec![
+            ("key1".to_owned(), vec![1]),
+            ("key2".to_owned(), vec![2]),
+            ("key1".to_owned(), vec![3]),
+        ].into_iter().collect();
+
+        assert_eq!(hm.len(), 2);
+        assert_eq!(hm.get("key1").unwrap(), &[3]);
+        assert_eq!(hm.get("key2").unwrap(), &[2]);
+        assert_eq!(hm.owned_count(), 2);
+    }
+
+    #[test]
+    fn extend_overwrites_existing() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_borrowed("key2", &[2]);
+
+        hm.extend(vec![
+            ("key2".to_owned(), vec![3]),
+            ("key3".to_owned(), vec![4]),
+        ]);
+
+        assert_eq!(hm.len(), 3);
+        assert_eq!(hm.get("key1").unwrap(), &[1]);
+        assert_eq!(hm.get("key2").unwrap(), &[3]);
+        assert_eq!(hm.get("key3").unwrap(), &[4]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key3"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn retain_preserves_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2]);
+        hm.insert_owned("key2".to_owned(), vec![3, 4]);
+        hm.insert_borrowed("key3", &[5]);
+        hm.insert_owned("key4".to_owned(), vec![6]);
+
+        hm.retain(|_, v| v.len() == 2);
+
+        assert_eq!(hm.len(), 2);
+        assert!(!hm.contains_key("key3"));
+        assert!(!hm.contains_key("key4"));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn drain_empties_and_retains_capacity() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_borrowed("key2", &[2]);
+        hm.insert_owned_borrowed_key("key3", vec![3]);
+
+        let capacity = hm.capacity();
+        let mut pairs: Vec<(String, Vec<u8>)> = hm.drain().collect();
+        pairs.sort();
+
+        assert_eq!(pairs, vec![
+            ("key1".to_owned(), vec![1]),
+            ("key2".to_owned(), vec![2]),
+            ("key3".to_owned(), vec![3]),
+        ]);
+        assert_eq!(hm.len(), 0);
+        assert_eq!(hm.capacity(), capacity);
+    }
+
+    #[test]
+    fn default_is_empty() {
+        assert!(CowHashMap::<str, [u8]>::default().is_empty());
+    }
+
+    #[test]
+    fn get_cloned_preserves_form() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        assert_eq!(hm.get_cloned("key1"), Some(vec![1, 2, 3]));
+        assert_eq!(hm.get_cloned("key2"), Some(vec![4, 5, 6]));
+        assert_eq!(hm.get_cloned("key3"), None);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn make_borrowed_replaces_owned() {
+        let backing = vec![1, 2, 3];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+
+        assert!(hm.make_borrowed("key1", &backing));
+        assert!(!hm.make_borrowed("key2", &backing));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert!(std::ptr::eq(hm.get("key1").unwrap(), backing.as_slice()));
+        assert_eq!(hm.len(), 1);
+    }
+
+    #[test]
+    fn try_reserve() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        assert_eq!(hm.try_reserve(16), Ok(()));
+        assert!(hm.capacity() >= 16);
+        assert_eq!(hm.try_reserve(usize::MAX), Err(TryReserveError::CapacityOverflow));
+    }
+
+    #[test]
+    fn shrink_to_keeps_floor() {
+        let backing: Vec<Vec<u8>> = (0..100).map(|i| vec![i]).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for (i, val) in backing.iter().enumerate() {
+            hm.insert_borrowed_owned_key(i.to_string(), val);
+        }
+
+        let capacity = hm.capacity();
+        hm.retain(|_, v| v[0] < 5);
+        hm.shrink_to(20);
+
+        assert_eq!(hm.len(), 5);
+        assert!(hm.capacity() >= 20);
+        assert!(hm.capacity() < capacity);
+
+        hm.shrink_to(0);
+        assert!(hm.capacity() >= hm.len());
+    }
+
+    #[test]
+    fn merge_resolves_collisions() {
+        let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm1.insert_borrowed("key1", &[1]);
+        hm1.insert_owned("key2".to_owned(), vec![2]);
+
+        let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm2.insert_borrowed("key1", &[10]);
+        hm2.insert_borrowed("key3", &[3]);
+        hm2.insert_owned("key4".to_owned(), vec![4]);
+
+        let mut collisions = Vec::new();
+        hm1.merge(hm2, |key, existing, incoming| {
+            collisions.push(key.to_owned());
+            existing[0] += incoming[0];
+        });
+
+        assert_eq!(collisions, vec!["key1".to_owned()]);
+        assert_eq!(hm1.len(), 4);
+        assert_eq!(hm1.get("key1").unwrap(), &[11]);
+        assert_eq!(hm1.get("key2").unwrap(), &[2]);
+        assert_eq!(hm1.get("key3").unwrap(), &[3]);
+        assert_eq!(hm1.get("key4").unwrap(), &[4]);
+        assert_eq!(hm1.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm1.entry_form("key3"), Some(Form::Borrowed));
+        assert_eq!(hm1.entry_form("key4"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn extend_borrowed_does_not_clone() {
+        let backing: Vec<(String, Vec<u8>)> = (0..100u8).map(|i| (i.to_string(), vec![i])).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        hm.extend_borrowed(backing.iter().map(|(k, v)| (k.as_str(), v.as_slice())));
+
+        assert_eq!(hm.len(), 100);
+        assert!(hm.inner.iter().all(|entry| matches!(entry, (Cow::Borrowed(_), Cow::Borrowed(_)))));
+
+        for (key, val) in backing.iter() {
+            assert_eq!(hm.entry_form(key), Some(Form::Borrowed));
+            assert_eq!(hm.get(key).unwrap(), val.as_slice());
+        }
+    }
+
+    #[test]
+    fn get_or_insert_with_hit_and_miss() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+
+        let mut calls = 0;
+        let val = hm.get_or_insert_with("key1".to_owned(), || { calls += 1; vec![0] });
+        val.push(4);
+
+        assert_eq!(calls, 0);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+
+        let val = hm.get_or_insert_with("key2".to_owned(), || { calls += 1; vec![5] });
+        assert_eq!(val, &vec![5]);
+        assert_eq!(calls, 1);
+        assert_eq!(hm.get("key2").unwrap(), &[5]);
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    fn borrow_and_mutate<'a>(hm: &mut CowHashMap<'a, str, [u8]>, val: &'a [u8]) -> usize {
+        let len = {
+            let hm_clone = hm.borrow_fields();
+            assert_eq!(hm.get("key1"), hm_clone.get("key1"));
+            hm_clone.len()
+        };
+
+        hm.insert_borrowed("key2", val);
+        len
+    }
+
+    #[test]
+    fn borrow_fields_scoped_borrow() {
+        let backing = vec![4, 5, 6];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+
+        {
+            let hm_clone = hm.borrow_fields();
+            assert_eq!(hm_clone.entry_form("key1"), Some(Form::Borrowed));
+            assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+            assert_eq!(hm.get("key1"), hm_clone.get("key1"));
+            assert_eq!(hm.len(), hm_clone.len());
+        }
+
+        assert_eq!(borrow_and_mutate(&mut hm, &backing), 1);
+        assert_eq!(hm.len(), 2);
+    }
+
+    #[test]
+    fn iter_forms_reports_value_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_owned_borrowed_key("key2", vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        let forms: std::collections::HashMap<&str, Form> = hm
+            .iter_forms()
+            .map(|(k, _, form)| (k, form))
+            .collect();
+
+        assert_eq!(forms.len(), 4);
+        assert_eq!(forms["key1"], Form::Owned);
+        assert_eq!(forms["key2"], Form::Owned);
+        assert_eq!(forms["key3"], Form::Borrowed);
+        assert_eq!(forms["key4"], Form::Borrowed);
+    }
+
+    #[test]
+    fn insert_all_cow_combinations() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        assert!(hm.insert(Cow::Owned("key1".to_owned()), Cow::Owned(vec![1])).is_none());
+        assert!(hm.insert(Cow::Borrowed("key2"), Cow::Owned(vec![2])).is_none());
+        assert!(hm.insert(Cow::Borrowed("key3"), Cow::Borrowed(&[3])).is_none());
+        assert!(hm.insert(Cow::Owned("key4".to_owned()), Cow::Borrowed(&[4])).is_none());
+
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key3"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key4"), Some(Form::Borrowed));
+        assert!(matches!(hm.inner.get_key_value("key2"), Some((Cow::Borrowed(_), _))));
+        assert!(matches!(hm.inner.get_key_value("key4"), Some((Cow::Owned(_), _))));
+
+        assert_eq!(hm.insert(Cow::Borrowed("key3"), Cow::Owned(vec![5])), Some(vec![3]));
+        assert_eq!(hm.insert(Cow::Borrowed("key1"), Cow::Borrowed(&[6])), Some(vec![1]));
+        assert_eq!(hm.len(), 4);
+    }
+
+    #[test]
+    fn from_std_hashmap() {
+        let mut std_hm: std::collections::HashMap<String, Vec<u8>> = std::collections::HashMap::new();
+        std_hm.insert("key1".to_owned(), vec![1, 2, 3]);
+        std_hm.insert("key2".to_owned(), vec![4, 5, 6]);
+
+        let hm: CowHashMap<str, [u8]> = CowHashMap::from_std_hashmap(std_hm);
+
+        assert_eq!(hm.len(), 2);
+        assert!(hm.capacity() >= 2);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+        assert_eq!(hm.owned_count(), 2);
+    }
+
+    #[test]
+    fn get_many_mut_disjoint_and_duplicate() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+
+        {
+            let [val1, val2] = hm.get_many_mut(["key1", "key2"]).unwrap();
+            val1.push(10);
+            val2.push(20);
+        }
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 10]);
+        assert_eq!(hm.get("key2").unwrap(), &[2, 20]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("key3"), Some(Form::Borrowed));
+
+        assert!(hm.get_many_mut(["key3", "key3"]).is_none());
+        assert!(hm.get_many_mut(["key3", "key4"]).is_none());
+        assert_eq!(hm.entry_form("key3"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn index_existing_key() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        assert_eq!(&hm["key1"], &[1, 2, 3]);
+        assert_eq!(&hm["key2"], &[4, 5, 6]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    #[should_panic(expected = "no entry found for key")]
+    fn index_missing_key() {
+        let hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        let _ = &hm["key"];
+    }
+
+    #[test]
+    fn keys_owned_outlive_map() {
+        let keys: Vec<String> = {
+            let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+            hm.insert_borrowed("key1", &[1, 2, 3]);
+            hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+            let mut keys: Vec<String> = hm.keys_owned().collect();
+            keys.sort();
+            keys
+        };
+
+        assert_eq!(keys, vec!["key1".to_owned(), "key2".to_owned()]);
+    }
+
+    #[test]
+    fn set_entry_form_upgrades_borrowed() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+
+        assert!(hm.set_entry_form("key1", Form::Owned));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert!(!hm.set_entry_form("key2", Form::Owned));
+    }
+
+    #[test]
+    fn set_entry_form_borrowed_does_not_downgrade() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+
+        assert!(hm.set_entry_form("key1", Form::Borrowed));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert!(!hm.set_entry_form("key2", Form::Borrowed));
+    }
+
+    #[test]
+    fn memory_size_hint_counts_owned_data() {
+        let backing = vec![0; 100];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &backing);
+        hm.insert_owned("key2".to_owned(), vec![0; 50]);
+
+        let table = hm.capacity() * std::mem::size_of::<(Cow<str>, Cow<[u8]>)>();
+        let owned = hm.inner.iter()
+            .filter_map(|(k, v)| match (k, v) {
+                (Cow::Owned(k), Cow::Owned(v)) => Some(k.capacity() + v.capacity()),
+                _ => None,
+            })
+            .sum::<usize>();
+
+        assert!(owned >= 54);
+        assert_eq!(hm.memory_size_hint(), table + owned);
+
+        hm.make_owned("key1");
+        assert!(hm.memory_size_hint() >= table + owned + 100);
+    }
+
+    #[test]
+    fn raw_entry_found_key() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+
+        match hm.raw_entry_mut().from_key("key1") {
+            RawEntryMut::Occupied(mut entry) => {
+                assert_eq!(entry.key(), "key1");
+                assert_eq!(entry.form(), Form::Borrowed);
+                entry.make_owned();
+                assert_eq!(entry.form(), Form::Owned);
+                entry.get_mut().push(4);
+            }
+
+            RawEntryMut::Vacant(_) => panic!("expected an occupied entry"),
+        }
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn raw_entry_insert_on_miss() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        let hash = hm.hasher().hash_one("key1");
+
+        match hm.raw_entry_mut().from_hash(hash, |k| k == "key1") {
+            RawEntryMut::Occupied(_) => panic!("expected a vacant entry"),
+            RawEntryMut::Vacant(entry) => {
+                entry.insert_owned_hashed_nocheck(hash, "key1".to_owned(), vec![1, 2, 3]);
+            }
+        }
+
+        hm.raw_entry_mut().from_key("key2").or_insert_borrowed("key2", &[4, 5, 6]);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+        assert_eq!(hm.entry_form("key2"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn diff_lists_all_categories() {
+        let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm1.insert_borrowed("same", &[1]);
+        hm1.insert_borrowed("changed", &[2]);
+        hm1.insert_owned("removed".to_owned(), vec![3]);
+
+        let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm2.insert_owned("same".to_owned(), vec![1]);
+        hm2.insert_owned("changed".to_owned(), vec![4]);
+        hm2.insert_borrowed("added", &[5]);
+
+        let diff = hm1.diff(&hm2);
+
+        assert_eq!(diff.added, vec![("added".to_owned(), vec![5])]);
+        assert_eq!(diff.removed, vec!["removed".to_owned()]);
+        assert_eq!(diff.changed, vec![("changed".to_owned(), vec![4])]);
+        assert!(!diff.is_empty());
+        assert!(hm1.diff(&hm1.borrow_fields()).is_empty());
+    }
+
+    #[test]
+    fn apply_patch_round_trip() {
+        let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm1.insert_borrowed("same", &[1]);
+        hm1.insert_borrowed("changed", &[2]);
+        hm1.insert_owned("removed".to_owned(), vec![3]);
+
+        let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm2.insert_owned("same".to_owned(), vec![1]);
+        hm2.insert_owned("changed".to_owned(), vec![4]);
+        hm2.insert_borrowed("added", &[5]);
+
+        let diff = hm1.diff(&hm2);
+        hm1.apply_patch(&diff);
+
+        assert_eq!(hm1, hm2);
+        assert_eq!(hm1.entry_form("added"), Some(Form::Owned));
+        assert_eq!(hm1.entry_form("changed"), Some(Form::Owned));
+        assert_eq!(hm1.entry_form("same"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn apply_patch_ignores_missing_removed_keys() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+
+        let patch = MapDiff::<str, [u8]> {
+            added: vec![],
+            removed: vec!["missing".to_owned()],
+            changed: vec![],
+        };
+
+        hm.apply_patch(&patch);
+
+        assert_eq!(hm.len(), 1);
+        assert_eq!(hm.get("key1").unwrap(), &[1]);
+    }
+
+    #[test]
+    fn retain_with_form_keeps_owned() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+        hm.insert_owned("key4".to_owned(), vec![4]);
+
+        hm.retain_with_form(|_, _, form| form == Form::Owned);
+
+        assert_eq!(hm.keys().collect::<HashSet<_>>(), set!["key2", "key4"]);
+        assert_eq!(hm.borrowed_count(), 0);
+    }
+
+    #[test]
+    fn equal_maps_hash_equally() {
+        use std::collections::hash_map::DefaultHasher;
+        use core::hash::Hasher;
+
+        fn hash_of<T: Hash>(value: &T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            value.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        let mut hm1: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm1.insert_borrowed("key1", &[1]);
+        hm1.insert_owned("key2".to_owned(), vec![2]);
+        hm1.insert_borrowed("key3", &[3]);
+
+        let mut hm2: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm2.insert_owned("key3".to_owned(), vec![3]);
+        hm2.insert_borrowed("key2", &[2]);
+        hm2.insert_owned("key1".to_owned(), vec![1]);
+
+        assert_eq!(hm1, hm2);
+        assert_eq!(hash_of(&hm1), hash_of(&hm2));
+
+        hm2.insert_owned("key1".to_owned(), vec![4]);
+        assert_ne!(hash_of(&hm1), hash_of(&hm2));
+    }
+
+    #[test]
+    fn split_by_form_separates_entries() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_owned_borrowed_key("key3", vec![3]);
+        hm.insert_borrowed_owned_key("key4".to_owned(), &[4]);
+
+        let (borrowed, owned) = hm.split_by_form();
+
+        assert_eq!(borrowed.keys().collect::<HashSet<_>>(), set!["key1", "key4"]);
+        assert_eq!(owned.keys().collect::<HashSet<_>>(), set!["key2", "key3"]);
+        assert!(borrowed.iter_forms().all(|(_, _, form)| form == Form::Borrowed));
+        assert!(owned.iter_forms().all(|(_, _, form)| form == Form::Owned));
+        assert!(matches!(owned.inner.get_key_value("key3"), Some((Cow::Borrowed(_), _))));
+        assert!(matches!(borrowed.inner.get_key_value("key4"), Some((Cow::Owned(_), _))));
+    }
+
+    #[test]
+    fn entry_or_default_counts() {
+        let one = 1;
+        let mut hm: CowHashMap<str, u64> = CowHashMap::new();
+        hm.insert_borrowed("existing", &one);
+
+        *hm.entry_or_default("missing".to_owned()) += 1;
+        *hm.entry_or_default("existing".to_owned()) += 1;
+
+        assert_eq!(hm.get("missing").unwrap(), &1);
+        assert_eq!(hm.get("existing").unwrap(), &2);
+        assert_eq!(hm.entry_form("existing"), Some(Form::Owned));
+        assert_eq!(one, 1);
+    }
+
+    #[test]
+    fn reserve_exact_is_tight() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.reserve_exact(100);
+
+        assert!(hm.capacity() >= 100);
+        assert!(hm.capacity() < 200);
+
+        let capacity = hm.capacity();
+        hm.insert_borrowed("key", &[1]);
+        hm.reserve_exact(1);
+
+        assert_eq!(hm.capacity(), capacity);
+    }
+
+    #[test]
+    fn get_pair_mut_reads_key_while_mutating() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("abc", &[1]);
+        hm.insert_owned("de".to_owned(), vec![2]);
+
+        for key in ["abc", "de"].iter() {
+            let (stored_key, val) = hm.get_pair_mut(key).unwrap();
+            assert_eq!(stored_key, *key);
+            val.push(stored_key.len() as u8);
+        }
+
+        assert_eq!(hm.get("abc").unwrap(), &[1, 3]);
+        assert_eq!(hm.get("de").unwrap(), &[2, 2]);
+        assert_eq!(hm.borrowed_count(), 0);
+        assert!(hm.get_pair_mut("missing").is_none());
+    }
+
+    #[test]
+    fn into_keys_and_values() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let mut keys: Vec<String> = hm.clone().into_keys().collect();
+        keys.sort();
+
+        let mut values: Vec<Vec<u8>> = hm.into_values().collect();
+        values.sort();
+
+        assert_eq!(keys, vec!["key1".to_owned(), "key2".to_owned()]);
+        assert_eq!(values, vec![vec![1], vec![2]]);
+    }
+
+    #[test]
+    fn from_borrowed_pairs_does_not_clone() {
+        let pairs: Vec<(String, Vec<u8>)> = (0..10)
+            .map(|i| (i.to_string(), vec![i as u8]))
+            .collect();
+
+        let hm: CowHashMap<str, [u8]> = CowHashMap::from_borrowed_pairs(&pairs);
+
+        assert_eq!(hm.len(), 10);
+        assert_eq!(hm.borrowed_count(), 10);
+        assert!(matches!(hm.inner.get_key_value("3"), Some((Cow::Borrowed(_), Cow::Borrowed(_)))));
+        assert_eq!(hm.get("3").unwrap(), &[3]);
+    }
+
+    #[test]
+    fn clone_owned_into_replaces_contents() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let mut dest: CowHashMap<str, [u8]> = CowHashMap::with_capacity(16);
+        dest.insert_borrowed("stale", &[0]);
+        let capacity = dest.capacity();
+
+        hm.clone_owned_into(&mut dest);
+
+        assert_eq!(dest, hm);
+        assert!(!dest.contains_key("stale"));
+        assert_eq!(dest.owned_count(), 2);
+        assert_eq!(dest.capacity(), capacity);
+    }
+
+    #[test]
+    fn contains_value_ignores_form() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        assert!(hm.contains_value(&[1]));
+        assert!(hm.contains_value(&[2]));
+        assert!(!hm.contains_value(&[3]));
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn find_key_by_value_reverse_lookup() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_owned("key3".to_owned(), vec![3]);
+
+        assert_eq!(hm.find_key_by_value(&[2]), Some("key2"));
+        assert_eq!(hm.find_key_by_value(&[1]), Some("key1"));
+        assert_eq!(hm.find_key_by_value(&[4]), None);
+    }
+
+    #[test]
+    fn replace_key_preserves_form() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        assert!(hm.replace_key("key1", "renamed1".to_owned()));
+        assert!(hm.replace_key("key2", "renamed2".to_owned()));
+        assert!(!hm.replace_key("missing", "renamed3".to_owned()));
+
+        assert_eq!(hm.keys().collect::<HashSet<_>>(), set!["renamed1", "renamed2"]);
+        assert_eq!(hm.entry_form("renamed1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("renamed2"), Some(Form::Owned));
+        assert_eq!(hm.get("renamed1").unwrap(), &[1]);
+    }
+
+    #[test]
+    fn try_insert_owned_vacant() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        hm.try_insert_owned("key1".to_owned(), vec![1]).unwrap().push(2);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn try_insert_owned_occupied() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+
+        match hm.try_insert_owned("key1".to_owned(), vec![2]) {
+            Ok(_) => panic!("expected the insertion to fail"),
+            Err(err) => {
+                assert_eq!(err.entry.key(), "key1");
+                assert_eq!(err.entry.get(), &[1]);
+                assert_eq!(err.value, vec![2]);
+                assert_eq!(
+                    err.to_string(),
+                    r#"failed to insert [2], key "key1" already exists with value [1]"#
+                );
+            }
+        }
+
+        assert_eq!(hm.get("key1").unwrap(), &[1]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn get_or_insert_borrowed_hit_and_miss() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("hit", &[1]);
+
+        assert_eq!(hm.get_or_insert_borrowed("hit".to_owned(), &[0]), &[1]);
+        assert_eq!(hm.get_or_insert_borrowed("miss".to_owned(), &[2]), &[2]);
+
+        assert_eq!(hm.entry_form("hit"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("miss"), Some(Form::Borrowed));
+        assert_eq!(hm.len(), 2);
+    }
+
+    #[test]
+    fn count_by_value_length() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2, 3]);
+        hm.insert_borrowed("key3", &[4, 5]);
+        hm.insert_owned("key4".to_owned(), vec![6, 7]);
+
+        let counts = hm.count_by(|_, v| v.len());
+
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[&1], 1);
+        assert_eq!(counts[&2], 3);
+        assert_eq!(hm.borrowed_count(), 2);
+    }
+
+    #[test]
+    fn clone_borrowed_is_fully_borrowed() {
+        let backing = vec![1];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &backing);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        {
+            let hm_clone = hm.clone_borrowed();
+
+            assert_eq!(hm_clone, hm);
+            assert!(hm_clone.inner.iter().all(|(k, v)| matches!((k, v), (Cow::Borrowed(_), Cow::Borrowed(_)))));
+            assert!(std::ptr::eq(hm_clone.get("key1").unwrap(), &backing[..]));
+            assert!(std::ptr::eq(hm_clone.get("key2").unwrap(), hm.get("key2").unwrap()));
+        }
+
+        hm.get_mut("key2").unwrap().push(3);
+        assert_eq!(hm.get("key2").unwrap(), &[2, 3]);
+    }
+
+    #[test]
+    fn borrowed_and_owned_entries_partition_map() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+
+        let borrowed: HashSet<_> = hm.borrowed_entries().map(|(k, _)| k).collect();
+        let owned: HashSet<_> = hm.owned_entries().map(|(k, _)| k).collect();
+
+        assert_eq!(borrowed, set!["key1", "key3"]);
+        assert_eq!(owned, set!["key2"]);
+        assert_eq!(hm.borrowed_entries().count() + hm.owned_entries().count(), hm.len());
+        assert_eq!(hm.borrowed_count(), 2);
+    }
+
+    #[test]
+    fn form_summary_tracks_mutation() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_borrowed("key2", &[2]);
+        hm.insert_owned("key3".to_owned(), vec![3]);
+
+        assert_eq!(hm.form_summary(), FormSummary { borrowed: 2, owned: 1, total: 3 });
+
+        hm.get_mut("key1").unwrap().push(4);
+
+        assert_eq!(hm.form_summary(), FormSummary { borrowed: 1, owned: 2, total: 3 });
+        assert_eq!(CowHashMap::<str, [u8]>::new().form_summary(), FormSummary { borrowed: 0, owned: 0, total: 0 });
+    }
+
+    #[test]
+    fn map_values_hex_encodes() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[0x01, 0xab]);
+        hm.insert_owned("key2".to_owned(), vec![0xff]);
+
+        let hex: CowHashMap<str, str> = hm.map_values(|v| {
+            v.iter().map(|b| format!("{:02x}", b)).collect()
+        });
+
+        assert_eq!(hex.get("key1").unwrap(), "01ab");
+        assert_eq!(hex.get("key2").unwrap(), "ff");
+        assert_eq!(hex.owned_count(), 2);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn filter_map_into_drops_and_rewrites() {
+        let backing: Vec<Vec<u8>> = (0..10).map(|i| vec![i]).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for (i, val) in backing.iter().enumerate() {
+            hm.insert_borrowed_owned_key(i.to_string(), val);
+        }
+
+        let hm = hm.filter_map_into(|k, mut v| {
+            if v[0] % 2 == 0 {
+                None
+            } else {
+                v[0] *= 10;
+                Some((format!("odd{}", k), v))
+            }
+        });
+
+        assert_eq!(hm.len(), 5);
+        assert_eq!(hm.owned_count(), 5);
+        assert_eq!(hm.get("odd3").unwrap(), &[30]);
+        assert!(!hm.contains_key("3"));
+        assert!(!hm.contains_key("odd4"));
+    }
+
+    #[test]
+    fn remove_all_counts_present_keys() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_borrowed("key3", &[3]);
+
+        assert_eq!(hm.remove_all(&["key1", "missing", "key2", "key1"]), 2);
+        assert_eq!(hm.len(), 1);
+        assert!(hm.contains_key("key3"));
+        assert_eq!(hm.remove_all(&[]), 0);
+    }
+
+    #[test]
+    fn partition_preserves_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("long_borrowed", &[1, 2]);
+        hm.insert_owned("long_owned".to_owned(), vec![3, 4]);
+        hm.insert_borrowed("short_borrowed", &[5]);
+        hm.insert_owned("short_owned".to_owned(), vec![6]);
+
+        let (long, short) = hm.partition(|_, v| v.len() > 1);
+
+        assert_eq!(long.keys().collect::<HashSet<_>>(), set!["long_borrowed", "long_owned"]);
+        assert_eq!(short.keys().collect::<HashSet<_>>(), set!["short_borrowed", "short_owned"]);
+        assert_eq!(long.entry_form("long_borrowed"), Some(Form::Borrowed));
+        assert_eq!(long.entry_form("long_owned"), Some(Form::Owned));
+        assert_eq!(short.entry_form("short_borrowed"), Some(Form::Borrowed));
+        assert_eq!(short.entry_form("short_owned"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn entry_ref_borrows_key_on_insert() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        hm.entry_ref("key1").or_insert_owned(vec![1]);
+
+        assert!(matches!(hm.inner.get_key_value("key1"), Some((Cow::Borrowed(_), Cow::Owned(_)))));
+        assert_eq!(hm.get("key1").unwrap(), &[1]);
+    }
+
+    #[test]
+    fn entry_ref_keeps_stored_key_on_hit() {
+        let lookup = String::from("key1");
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        let stored = hm.get_key_value("key1").unwrap().0 as *const str;
+
+        hm.entry_ref(&lookup).and_modify(|v| v.push(2)).or_insert_owned(vec![]);
+
+        assert!(matches!(hm.inner.get_key_value("key1"), Some((Cow::Owned(_), Cow::Owned(_)))));
+        assert!(std::ptr::eq(hm.get_key_value("key1").unwrap().0, stored));
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2]);
+    }
+
+    #[test]
+    fn update_in_place_materializes_once() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+
+        let mut seen = Vec::new();
+        let found = hm.update_in_place("key1", |v| {
+            seen = v.clone();
+            v.push(4);
+        });
+
+        assert!(found);
+        assert_eq!(seen, vec![1, 2, 3]);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3, 4]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Owned));
+
+        let mut called = false;
+        assert!(!hm.update_in_place("missing", |_| called = true));
+        assert!(!called);
+    }
+
+    #[test]
+    fn peek_reports_value_and_form() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let (val, form) = hm.peek("key1").unwrap();
+        assert_eq!(val, &[1]);
+        assert_eq!(form, Form::Borrowed);
+
+        assert_eq!(hm.peek("key2"), Some((&[2][..], Form::Owned)));
+        assert_eq!(hm.peek("missing"), None);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn from_cow_map_keeps_forms() {
+        let mut inner: HashMap<Cow<str>, Cow<[u8]>> = HashMap::new();
+        inner.insert(Cow::Borrowed("key1"), Cow::Borrowed(&[1]));
+        inner.insert(Cow::Owned("key2".to_owned()), Cow::Owned(vec![2]));
+        inner.insert(Cow::Owned("key3".to_owned()), Cow::Borrowed(&[3]));
+
+        let hm = CowHashMap::from_cow_map(inner);
+
+        assert_eq!(hm.len(), 3);
+        assert_eq!(hm.form_summary(), FormSummary { borrowed: 2, owned: 1, total: 3 });
+        assert_eq!(hm.peek("key2"), Some((&[2][..], Form::Owned)));
+        assert!(matches!(hm.inner.get_key_value("key3"), Some((Cow::Owned(_), Cow::Borrowed(_)))));
+    }
+
+    #[test]
+    fn into_inner_preserves_contents() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let inner = hm.into_inner();
+
+        assert_eq!(inner.len(), 2);
+        assert!(matches!(inner.get("key1"), Some(Cow::Borrowed(&[1]))));
+        assert!(matches!(inner.get("key2"), Some(Cow::Owned(v)) if v == &vec![2]));
+
+        let hm = CowHashMap::from_cow_map(inner);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn forms_of_follows_input_order() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let keys = ["missing", "key2", "key1", "key2"];
+
+        assert_eq!(
+            hm.forms_of(keys.iter().copied()),
+            vec![None, Some(Form::Owned), Some(Form::Borrowed), Some(Form::Owned)]
+        );
+        assert!(hm.forms_of(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn evict_borrowed_keeps_owned() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+        hm.insert_borrowed_owned_key("key3".to_owned(), &[3]);
+        hm.insert_owned_borrowed_key("key4", vec![4]);
+
+        assert_eq!(hm.evict_borrowed(), 2);
+        assert_eq!(hm.keys().collect::<HashSet<_>>(), set!["key2", "key4"]);
+        assert_eq!(hm.get("key4").unwrap(), &[4]);
+        assert_eq!(hm.evict_borrowed(), 0);
+    }
+
+    #[test]
+    fn materialize_if_only_flips_matching_borrowed() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("big_borrowed", &[1, 2, 3]);
+        hm.insert_borrowed("small_borrowed", &[4]);
+        hm.insert_owned("big_owned".to_owned(), vec![5, 6, 7]);
+
+        let mut visited = Vec::new();
+        let converted = hm.materialize_if(|k, v| {
+            visited.push(k.to_owned());
+            v.len() > 1
+        });
+        visited.sort();
+
+        assert_eq!(converted, 1);
+        assert_eq!(visited, vec!["big_borrowed".to_owned(), "small_borrowed".to_owned()]);
+        assert_eq!(hm.entry_form("big_borrowed"), Some(Form::Owned));
+        assert_eq!(hm.entry_form("small_borrowed"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("big_owned"), Some(Form::Owned));
+        assert_eq!(hm.get("big_borrowed").unwrap(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn get_multi_preserves_order() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let vals = hm.get_multi(["key2", "missing", "key1", "key2"]);
+
+        assert_eq!(vals, [Some(&[2][..]), None, Some(&[1][..]), Some(&[2][..])]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn extend_cow_preserves_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        let pairs: Vec<(Cow<str>, Cow<[u8]>)> = vec![
+            (Cow::Borrowed("key1"), Cow::Borrowed(&[1])),
+            (Cow::Owned("key2".to_owned()), Cow::Owned(vec![2])),
+            (Cow::Borrowed("key3"), Cow::Owned(vec![3])),
+            (Cow::Owned("key4".to_owned()), Cow::Borrowed(&[4])),
+        ];
+
+        hm.extend_cow(pairs);
+
+        assert_eq!(hm.len(), 4);
+        assert_eq!(
+            hm.forms_of(vec!["key1", "key2", "key3", "key4"]),
+            vec![Some(Form::Borrowed), Some(Form::Owned), Some(Form::Owned), Some(Form::Borrowed)]
+        );
+        assert!(matches!(hm.inner.get_key_value("key3"), Some((Cow::Borrowed(_), _))));
+        assert!(matches!(hm.inner.get_key_value("key4"), Some((Cow::Owned(_), _))));
+    }
+
+    #[test]
+    fn compact_owns_and_shrinks() {
+        let backing: Vec<String> = (0..10).map(|i| i.to_string()).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::with_capacity(1000);
+
+        for key in backing.iter() {
+            hm.insert_borrowed(key, &[1]);
+        }
+
+        hm.compact();
+
+        assert_eq!(hm.len(), 10);
+        assert!(hm.inner.iter().all(|(k, v)| matches!((k, v), (Cow::Owned(_), Cow::Owned(_)))));
+        assert!(hm.capacity() >= hm.len());
+        assert!(hm.capacity() < 2 * hm.len());
+    }
+
+    #[test]
+    fn form_or_defaults_missing_keys() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+
+        assert_eq!(hm.form_or("key1", Form::Borrowed), Form::Owned);
+        assert_eq!(hm.form_or("missing", Form::Borrowed), Form::Borrowed);
+    }
+
+    #[test]
+    fn extract_if_removes_matching() {
+        let backing: Vec<Vec<u8>> = (0..10).map(|i| vec![i]).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for (i, val) in backing.iter().enumerate() {
+            if i % 3 == 0 {
+                hm.insert_owned(i.to_string(), val.clone());
+            } else {
+                hm.insert_borrowed_owned_key(i.to_string(), val);
+            }
+        }
+
+        let mut extracted: Vec<(String, Vec<u8>)> = hm.extract_if(|_, v| v[0] % 2 == 0).collect();
+        extracted.sort();
+
+        assert_eq!(extracted.len(), 5);
+        assert_eq!(extracted[0], ("0".to_owned(), vec![0]));
+        assert!(extracted.iter().all(|(_, v)| v[0] % 2 == 0));
+
+        assert_eq!(hm.len(), 5);
+        assert!(hm.values().all(|v| v[0] % 2 == 1));
+        assert_eq!(hm.entry_form("1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("3"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn sorted_iter_is_lexicographic() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for key in ["delta", "alpha", "charlie", "bravo", "echo"].iter() {
+            hm.insert_borrowed(key, &[1]);
+        }
+
+        hm.insert_owned("aardvark".to_owned(), vec![2]);
+
+        let keys: Vec<&str> = hm.sorted_iter().map(|(k, _)| k).collect();
+
+        assert_eq!(keys, vec!["aardvark", "alpha", "bravo", "charlie", "delta", "echo"]);
+        assert_eq!(hm.borrowed_count(), 5);
+    }
+
+    #[test]
+    fn min_and_max_key_value() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        assert_eq!(hm.min_key_value(), None);
+        assert_eq!(hm.max_key_value(), None);
+
+        hm.insert_borrowed("m", &[1]);
+        hm.insert_owned("a".to_owned(), vec![2]);
+        hm.insert_borrowed("z", &[3]);
+        hm.insert_owned("q".to_owned(), vec![4]);
+
+        assert_eq!(hm.min_key_value(), Some(("a", &[2][..])));
+        assert_eq!(hm.max_key_value(), Some(("z", &[3][..])));
+        assert_eq!(hm.entry_form("z"), Some(Form::Borrowed));
+    }
+
+    #[test]
+    fn get_or_insert_ref_borrows_on_miss() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("hit".to_owned(), vec![1]);
+
+        assert_eq!(hm.get_or_insert_ref("miss", &[2]), &[2]);
+        assert_eq!(hm.get_or_insert_ref("hit", &[3]), &[1]);
+
+        assert!(matches!(hm.inner.get_key_value("miss"), Some((Cow::Borrowed(_), Cow::Borrowed(_)))));
+        assert!(matches!(hm.inner.get_key_value("hit"), Some((Cow::Owned(_), Cow::Owned(_)))));
+    }
+
+    #[test]
+    fn insert_owned_capped_rejects_new_key_at_cap() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let err = hm.insert_owned_capped("key3".to_owned(), vec![3], 2).unwrap_err();
+
+        assert_eq!(err, CapacityExceeded { max_len: 2 });
+        assert_eq!(err.to_string(), "map is already at its maximum length of 2");
+        assert_eq!(hm.len(), 2);
+        assert!(!hm.contains_key("key3"));
+    }
+
+    #[test]
+    fn insert_owned_capped_allows_existing_key_at_cap() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        assert_eq!(hm.insert_owned_capped("key1".to_owned(), vec![4], 2), Ok(Some(vec![1])));
+        assert_eq!(hm.insert_owned_capped("key3".to_owned(), vec![3], 3), Ok(None));
+        assert_eq!(hm.len(), 3);
+        assert_eq!(hm.get("key1").unwrap(), &[4]);
+    }
+
+    #[test]
+    fn swap_values_moves_forms() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        assert!(hm.swap_values("key1", "key2"));
+
+        assert_eq!(hm.peek("key1"), Some((&[2][..], Form::Owned)));
+        assert_eq!(hm.peek("key2"), Some((&[1][..], Form::Borrowed)));
+
+        assert!(hm.swap_values("key1", "key1"));
+        assert!(!hm.swap_values("key1", "missing"));
+        assert!(!hm.swap_values("missing", "missing"));
+        assert_eq!(hm.peek("key1"), Some((&[2][..], Form::Owned)));
+    }
+
+    #[test]
+    fn eq_std_hashmap() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        let mut expected: std::collections::HashMap<String, Vec<u8>> = std::collections::HashMap::new();
+        expected.insert("key1".to_owned(), vec![1]);
+        expected.insert("key2".to_owned(), vec![2]);
+
+        assert_eq!(hm, expected);
+
+        expected.insert("key2".to_owned(), vec![3]);
+        assert_ne!(hm, expected);
+
+        expected.insert("key2".to_owned(), vec![2]);
+        expected.insert("key3".to_owned(), vec![3]);
+        assert_ne!(hm, expected);
+
+        expected.remove("key3");
+        expected.remove("key1");
+        expected.insert("other".to_owned(), vec![1]);
+        assert_ne!(hm, expected);
+    }
+
+    #[test]
+    fn fold_by_form_counts_bytes() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_borrowed("key2", &[4]);
+        hm.insert_owned("key3".to_owned(), vec![5, 6]);
+
+        let (borrowed, owned) = hm.fold_by_form(0, 0, |acc, k, v| acc + k.len() + v.len());
+
+        assert_eq!(borrowed, 4 + 3 + 4 + 1);
+        assert_eq!(owned, 4 + 2);
+
+        let (borrowed_keys, owned_keys) = hm.fold_by_form(Vec::new(), Vec::new(), |mut acc, k, _| {
+            acc.push(k.to_owned());
+            acc
+        });
+
+        assert_eq!(borrowed_keys.len(), 2);
+        assert_eq!(owned_keys, vec!["key3".to_owned()]);
+    }
+
+    #[test]
+    fn extend_owned_reserved_grows_once() {
+        let pairs: Vec<(String, Vec<u8>)> = (0..100)
+            .map(|i| (i.to_string(), vec![i as u8]))
+            .collect();
+
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.extend_owned_reserved(pairs);
+
+        let expected = CowHashMap::<str, [u8]>::with_capacity(100).capacity();
+
+        assert_eq!(hm.len(), 100);
+        assert_eq!(hm.capacity(), expected);
+        assert_eq!(hm.get("42").unwrap(), &[42]);
+    }
+
+    #[test]
+    fn or_insert_owned_with_key_uses_key() {
+        let one = 1;
+        let mut hm: CowHashMap<str, usize> = CowHashMap::new();
+        hm.insert_borrowed("existing", &one);
+
+        assert_eq!(*hm.entry("hello".to_owned()).or_insert_owned_with_key(|k| k.len()), 5);
+
+        let mut called = false;
+        *hm.entry("existing".to_owned()).or_insert_owned_with_key(|_| {
+            called = true;
+            0
+        }) += 1;
+
+        assert!(!called);
+        assert_eq!(hm.get("hello").unwrap(), &5);
+        assert_eq!(hm.get("existing").unwrap(), &2);
+    }
+
+    #[test]
+    fn duplicate_value_groups_reports_shared_values() {
+        let shared = vec![1, 2, 3];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &shared);
+        hm.insert_owned("key2".to_owned(), vec![1, 2, 3]);
+        hm.insert_owned("key3".to_owned(), vec![4, 5, 6]);
+
+        let mut groups = hm.duplicate_value_groups();
+        assert_eq!(groups.len(), 1);
+
+        groups[0].sort_unstable();
+        assert_eq!(groups[0], vec!["key1", "key2"]);
+        assert_eq!(hm.entry_form("key1").unwrap(), Form::Borrowed);
+    }
+
+    #[test]
+    fn duplicate_value_groups_empty_when_unique() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1]);
+        hm.insert_owned("key2".to_owned(), vec![2]);
+
+        assert!(hm.duplicate_value_groups().is_empty());
+    }
+
+    #[test]
+    fn range_half_open_over_str_keys() {
+        use std::ops::Bound;
+
+        let one = vec![1];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("apple", &one);
+        hm.insert_owned("banana".to_owned(), vec![2]);
+        hm.insert_owned("cherry".to_owned(), vec![3]);
+        hm.insert_owned("date".to_owned(), vec![4]);
+
+        let keys: Vec<&str> = hm.range((Bound::Included("apple"), Bound::Excluded("cherry")))
+            .map(|(k, _)| k)
+            .collect();
+
+        assert_eq!(keys, vec!["apple", "banana"]);
+        assert_eq!(hm.entry_form("apple").unwrap(), Form::Borrowed);
+    }
+
+    #[test]
+    fn range_over_sized_keys() {
+        let mut hm: CowHashMap<u32, u32> = CowHashMap::new();
+
+        for i in 0..10 {
+            hm.insert_owned(i, i * 2);
+        }
+
+        let vals: Vec<u32> = hm.range(3..6).map(|(_, v)| *v).collect();
+        assert_eq!(vals, vec![6, 8, 10]);
+    }
+
+    #[test]
+    fn diff_forms_detects_get_mut() {
+        let a = vec![1, 2, 3];
+        let b = vec![4, 5, 6];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &a);
+        hm.insert_borrowed("key2", &b);
+        hm.insert_owned("key3".to_owned(), vec![7]);
+
+        let snapshot = hm.form_snapshot();
+        assert_eq!(snapshot.len(), 3);
+        assert!(hm.diff_forms(&snapshot).is_empty());
+
+        hm.get_mut("key2").unwrap().push(7);
+        hm.remove("key3");
+        hm.insert_owned("key4".to_owned(), vec![8]);
+
+        assert_eq!(hm.diff_forms(&snapshot), vec!["key2"]);
+    }
+
+    #[test]
+    fn rebind_borrowed_after_materializing() {
+        let mut source: std::collections::HashMap<String, Vec<u8>> = std::collections::HashMap::new();
+        source.insert("key1".to_owned(), vec![1, 2, 3]);
+        source.insert("key2".to_owned(), vec![4, 5, 6]);
+
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for (k, v) in source.iter() {
+            hm.insert_borrowed(k, v);
+        }
+
+        hm.make_all_owned();
+        assert_eq!(hm.owned_count(), 2);
+
+        let capacity = hm.capacity();
+        assert_eq!(hm.rebind_borrowed(|k| source.get(k).map(|v| v.as_slice())), 2);
+
+        assert_eq!(hm.borrowed_count(), 2);
+        assert_eq!(hm.capacity(), capacity);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn rebind_borrowed_skips_missing_sources() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+
+        assert_eq!(hm.rebind_borrowed(|_| None), 0);
+        assert_eq!(hm.entry_form("key1").unwrap(), Form::Owned);
+    }
+
+    #[test]
+    fn drain_owned_keeps_borrowed() {
+        let a = vec![1, 2, 3];
+        let b = vec![4, 5, 6];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &a);
+        hm.insert_borrowed("key2", &b);
+        hm.insert_owned("key3".to_owned(), vec![7]);
+        hm.get_mut("key2").unwrap().push(8);
+
+        let mut drained: Vec<(String, Vec<u8>)> = hm.drain_owned().collect();
+        drained.sort_unstable();
+
+        assert_eq!(drained, vec![
+            ("key2".to_owned(), vec![4, 5, 6, 8]),
+            ("key3".to_owned(), vec![7]),
+        ]);
+
+        assert_eq!(hm.len(), 1);
+        assert_eq!(hm.entry_form("key1").unwrap(), Form::Borrowed);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn get_mut_if_owned_owned() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_owned("key1".to_owned(), vec![1, 2, 3]);
+
+        *hm.get_mut_if_owned("key1").unwrap() = vec![4];
+        assert_eq!(hm.get("key1").unwrap(), &[4]);
+    }
+
+    #[test]
+    fn get_mut_if_owned_borrowed() {
+        let val = vec![1, 2, 3];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &val);
+
+        assert!(hm.get_mut_if_owned("key1").is_none());
+        assert_eq!(hm.entry_form("key1").unwrap(), Form::Borrowed);
+    }
+
+    #[test]
+    fn get_mut_if_owned_missing() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        assert!(hm.get_mut_if_owned("key1").is_none());
+    }
+
+    #[test]
+    fn to_ref_map_reads_through() {
+        let val = vec![1, 2, 3];
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &val);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let refs = hm.to_ref_map();
+
+        assert_eq!(refs.len(), 2);
+        assert_eq!(refs.get("key1"), Some(&&[1, 2, 3][..]));
+        assert_eq!(refs.get("key2"), Some(&&[4, 5, 6][..]));
+        assert!(!refs.contains_key("key3"));
+        assert_eq!(hm.entry_form("key1").unwrap(), Form::Borrowed);
+    }
+
+    #[test]
+    fn insert_zip_borrowed_matching_lengths() {
+        let keys = vec!["key1".to_owned(), "key2".to_owned()];
+        let vals = vec![vec![1, 2, 3], vec![4, 5, 6]];
+
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        assert_eq!(hm.insert_zip_borrowed(&keys, &vals), Ok(()));
+
+        assert_eq!(hm.len(), 2);
+        assert_eq!(hm.borrowed_count(), 2);
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn insert_zip_borrowed_mismatched_lengths() {
+        let keys = vec!["key1".to_owned(), "key2".to_owned()];
+        let vals = vec![vec![1, 2, 3]];
+
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        let err = hm.insert_zip_borrowed(&keys, &vals).unwrap_err();
+
+        assert_eq!(err, LengthMismatch { keys: 2, values: 1 });
+        assert_eq!(err.to_string(), "key and value slices have different lengths (2 and 1)");
+        assert!(hm.is_empty());
+    }
+
+    #[test]
+    fn clone_preserving_forms_static() {
+        static VAL: [u8; 3] = [1, 2, 3];
+
+        let mut hm: CowHashMap<'static, str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &VAL);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        let hm_clone = {
+            let original = hm;
+            original.clone_preserving_forms()
+        };
+
+        assert_eq!(hm_clone.entry_form("key1").unwrap(), Form::Borrowed);
+        assert_eq!(hm_clone.entry_form("key2").unwrap(), Form::Owned);
+        assert!(std::ptr::eq(hm_clone.get("key1").unwrap(), &VAL[..]));
+        assert_eq!(hm_clone.get("key2").unwrap(), &[4, 5, 6]);
+    }
+
+    #[test]
+    fn evict_while_over_reaches_target() {
+        let mut hm: CowHashMap<u32, u32> = CowHashMap::new();
+
+        for i in 0..10 {
+            hm.insert_owned(i, i);
+        }
+
+        let evicted = hm.evict_while_over(4, |hm| hm.max_key_value().map(|(k, _)| *k));
+
+        assert_eq!(evicted, 6);
+        assert_eq!(hm.len(), 4);
+        assert_eq!(hm.max_key_value(), Some((&3, &3)));
+    }
+
+    #[test]
+    fn evict_while_over_stops_on_missing_key() {
+        let mut hm: CowHashMap<u32, u32> = CowHashMap::new();
+        hm.insert_owned(1, 1);
+        hm.insert_owned(2, 2);
+
+        assert_eq!(hm.evict_while_over(0, |_| Some(3)), 0);
+        assert_eq!(hm.evict_while_over(0, |_| None), 0);
+        assert_eq!(hm.evict_while_over(5, |_| unreachable!()), 0);
+        assert_eq!(hm.len(), 2);
+    }
+}
+
+#[cfg(all(test, not(feature = "std")))]
+mod no_std_tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn insert_and_get() {
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+        hm.insert_borrowed("key1", &[1, 2, 3]);
+        hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+
+        assert_eq!(hm.get("key1").unwrap(), &[1, 2, 3]);
+        assert_eq!(hm.get("key2").unwrap(), &[4, 5, 6]);
+        assert_eq!(hm.entry_form("key1"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("key2"), Some(Form::Owned));
+    }
 }
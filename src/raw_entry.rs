@@ -0,0 +1,247 @@
+// Copyright 2019 Octavian Oncescu
+
+use hashbrown::hash_map::{self, DefaultHashBuilder};
+use alloc::borrow::{Cow, ToOwned};
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use crate::Form;
+
+/// A builder for computing where in a `CowHashMap` a key-value
+/// pair would be stored.
+///
+/// This is constructed from the `raw_entry_mut` method on `CowHashMap`.
+pub struct RawEntryBuilderMut<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    pub(crate) inner: hash_map::RawEntryBuilderMut<'b, Cow<'a, K>, Cow<'a, V>, S>
+}
+
+/// A view into a single entry in a `CowHashMap`, which may either
+/// be vacant or occupied, obtained through a `RawEntryBuilderMut`.
+pub enum RawEntryMut<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    /// An occupied entry.
+    Occupied(RawOccupiedEntryMut<'b, 'a, K, V, S>),
+
+    /// A vacant entry.
+    Vacant(RawVacantEntryMut<'b, 'a, K, V, S>),
+}
+
+/// A view into an occupied entry in a `CowHashMap`, obtained
+/// through a `RawEntryBuilderMut`.
+pub struct RawOccupiedEntryMut<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    inner: hash_map::RawOccupiedEntryMut<'b, Cow<'a, K>, Cow<'a, V>, S>
+}
+
+/// A view into a vacant entry in a `CowHashMap`, obtained
+/// through a `RawEntryBuilderMut`.
+pub struct RawVacantEntryMut<'b, 'a, K, V, S = DefaultHashBuilder>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    inner: hash_map::RawVacantEntryMut<'b, Cow<'a, K>, Cow<'a, V>, S>
+}
+
+impl<'b, 'a, K, V, S> RawEntryBuilderMut<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Creates a `RawEntryMut` from the given key.
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_key(self, key: &K) -> RawEntryMut<'b, 'a, K, V, S> {
+        RawEntryMut::new(self.inner.from_key(key))
+    }
+
+    /// Creates a `RawEntryMut` from the given key and its
+    /// precomputed hash.
+    ///
+    /// The hash must have been computed with the hasher of the map,
+    /// otherwise the lookup may miss an existing entry.
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_key_hashed_nocheck(self, hash: u64, key: &K) -> RawEntryMut<'b, 'a, K, V, S> {
+        RawEntryMut::new(self.inner.from_key_hashed_nocheck(hash, key))
+    }
+
+    /// Creates a `RawEntryMut` from the given hash and matching function.
+    #[inline]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_hash<F>(self, hash: u64, mut is_match: F) -> RawEntryMut<'b, 'a, K, V, S>
+        where F: FnMut(&K) -> bool
+    {
+        RawEntryMut::new(self.inner.from_hash(hash, |k| is_match(k.borrow())))
+    }
+}
+
+impl<'b, 'a, K, V, S> RawEntryMut<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    #[inline]
+    fn new(inner: hash_map::RawEntryMut<'b, Cow<'a, K>, Cow<'a, V>, S>) -> Self {
+        match inner {
+            hash_map::RawEntryMut::Occupied(inner) => RawEntryMut::Occupied(RawOccupiedEntryMut { inner }),
+            hash_map::RawEntryMut::Vacant(inner) => RawEntryMut::Vacant(RawVacantEntryMut { inner }),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the given owned
+    /// key and value if empty, and returns a mutable reference to the
+    /// owned form of the value in the entry.
+    ///
+    /// If the entry is occupied by a borrowed value, it will be cloned.
+    #[inline]
+    pub fn or_insert_owned(self, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned) -> &'b mut <V as ToOwned>::Owned {
+        match self {
+            RawEntryMut::Occupied(entry) => entry.into_mut(),
+            RawEntryMut::Vacant(entry) => entry.insert_owned(key, value),
+        }
+    }
+
+    /// Ensures a value is in the entry by inserting the given borrowed
+    /// key and value if empty, and returns a reference to the value
+    /// in the entry.
+    ///
+    /// This function does not alter the form of an occupied entry.
+    #[inline]
+    pub fn or_insert_borrowed(self, key: &'a K, value: &'a V) -> &'b V {
+        match self {
+            RawEntryMut::Occupied(entry) => entry.into_ref(),
+            RawEntryMut::Vacant(entry) => entry.insert_borrowed(key, value),
+        }
+    }
+}
+
+impl<'b, 'a, K, V, S> RawOccupiedEntryMut<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Returns a reference to this entry's key.
+    #[inline]
+    pub fn key(&self) -> &K {
+        self.inner.key().borrow()
+    }
+
+    /// Returns a reference to the value in the entry.
+    ///
+    /// This does not alter the form of the entry.
+    #[inline]
+    pub fn get(&self) -> &V {
+        self.inner.get().as_ref()
+    }
+
+    /// Returns a mutable reference to the owned form of the value
+    /// in the entry.
+    ///
+    /// If the value is borrowed, it will be cloned.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut <V as ToOwned>::Owned {
+        self.inner.get_mut().to_mut()
+    }
+
+    /// Converts the entry into a mutable reference to the owned form
+    /// of its value, with a lifetime bound to the map itself.
+    ///
+    /// If the value is borrowed, it will be cloned.
+    #[inline]
+    pub fn into_mut(self) -> &'b mut <V as ToOwned>::Owned {
+        self.inner.into_mut().to_mut()
+    }
+
+    /// Converts the entry into a reference to its value, with a
+    /// lifetime bound to the map itself.
+    ///
+    /// This does not alter the form of the entry.
+    #[inline]
+    pub fn into_ref(self) -> &'b V {
+        self.inner.into_mut()
+    }
+
+    /// Returns the form in which the value of the entry is stored.
+    #[inline]
+    pub fn form(&self) -> Form {
+        Form::of(self.inner.get())
+    }
+
+    /// Makes the value of the entry owned, if it isn't so already.
+    ///
+    /// This function does not do anything if the value is already in owned
+    /// form.
+    #[inline]
+    pub fn make_owned(&mut self) {
+        self.inner.get_mut().to_mut();
+    }
+
+    /// Sets the value of the entry in owned form, and returns the
+    /// entry's old value. If the old value is borrowed, it will be
+    /// cloned and then returned.
+    #[inline]
+    pub fn insert_owned(&mut self, value: <V as ToOwned>::Owned) -> <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Owned(value)).into_owned()
+    }
+
+    /// Sets the value of the entry in borrowed form, and returns the
+    /// entry's old value. If the old value is borrowed, it will be
+    /// cloned and then returned.
+    #[inline]
+    pub fn insert_borrowed(&mut self, value: &'a V) -> <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Borrowed(value)).into_owned()
+    }
+
+    /// Takes the key and value out of the entry, and returns them
+    /// in owned form.
+    #[inline]
+    pub fn remove_entry(self) -> (<K as ToOwned>::Owned, <V as ToOwned>::Owned) {
+        let (k, v) = self.inner.remove_entry();
+        (k.into_owned(), v.into_owned())
+    }
+}
+
+impl<'b, 'a, K, V, S> RawVacantEntryMut<'b, 'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher,
+{
+    /// Sets the key and value of the entry in owned form, and returns
+    /// a mutable reference to the value.
+    #[inline]
+    pub fn insert_owned(self, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned) -> &'b mut <V as ToOwned>::Owned {
+        self.inner.insert(Cow::Owned(key), Cow::Owned(value)).1.to_mut()
+    }
+
+    /// Sets the key and value of the entry in borrowed form, and
+    /// returns a reference to the value.
+    #[inline]
+    pub fn insert_borrowed(self, key: &'a K, value: &'a V) -> &'b V {
+        self.inner.insert(Cow::Borrowed(key), Cow::Borrowed(value)).1
+    }
+
+    /// Sets the key and value of the entry in owned form using the
+    /// precomputed hash of the key, and returns a mutable reference
+    /// to the value.
+    ///
+    /// The hash must have been computed with the hasher of the map.
+    #[inline]
+    pub fn insert_owned_hashed_nocheck(self, hash: u64, key: <K as ToOwned>::Owned, value: <V as ToOwned>::Owned) -> &'b mut <V as ToOwned>::Owned {
+        self.inner.insert_hashed_nocheck(hash, Cow::Owned(key), Cow::Owned(value)).1.to_mut()
+    }
+
+    /// Sets the key and value of the entry in borrowed form using the
+    /// precomputed hash of the key, and returns a reference to the value.
+    ///
+    /// The hash must have been computed with the hasher of the map.
+    #[inline]
+    pub fn insert_borrowed_hashed_nocheck(self, hash: u64, key: &'a K, value: &'a V) -> &'b V {
+        self.inner.insert_hashed_nocheck(hash, Cow::Borrowed(key), Cow::Borrowed(value)).1
+    }
+}
@@ -0,0 +1,88 @@
+// Copyright 2019 Octavian Oncescu
+
+use ::rayon::iter::{IntoParallelIterator, ParallelIterator};
+use alloc::borrow::ToOwned;
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+use crate::CowHashMap;
+
+impl<'a, K, V, S> CowHashMap<'a, K, V, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned + Sync,
+          V: ToOwned + ?Sized + Sync,
+          <K as ToOwned>::Owned: Sync,
+          <V as ToOwned>::Owned: Sync,
+          S: BuildHasher + Sync,
+{
+    /// Returns a parallel iterator visiting all key-value pairs in arbitrary order.
+    /// 
+    /// This does not alter the form of any of the entries. This function is
+    /// only available with the `rayon` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use rayon::prelude::*;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let total_len: usize = hm.par_iter().map(|(k, v)| k.len() + v.len()).sum();
+    /// assert_eq!(total_len, 14);
+    /// ```
+    #[inline]
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (&K, &V)> {
+        (&self.inner).into_par_iter().map(|(k, v)| (k.borrow(), v.as_ref()))
+    }
+
+    /// Returns a parallel iterator over the values of the map.
+    /// 
+    /// This does not alter the form of any of the entries. This function is
+    /// only available with the `rayon` feature.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use hashcow::CowHashMap;
+    /// use rayon::prelude::*;
+    /// 
+    /// let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+    /// hm.insert_borrowed("key1", &[1, 2, 3]);
+    /// hm.insert_owned("key2".to_owned(), vec![4, 5, 6]);
+    /// 
+    /// let total_len: usize = hm.par_values().map(|v| v.len()).sum();
+    /// assert_eq!(total_len, 6);
+    /// ```
+    #[inline]
+    pub fn par_values(&self) -> impl ParallelIterator<Item = &V> {
+        self.inner.par_values().map(|v| v.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Form, CowHashMap};
+    use rayon::prelude::*;
+
+    #[test]
+    fn parallel_sum_matches_serial() {
+        let backing: Vec<Vec<u8>> = (0..1000).map(|i| vec![0; i % 17]).collect();
+        let mut hm: CowHashMap<str, [u8]> = CowHashMap::new();
+
+        for (i, val) in backing.iter().enumerate() {
+            if i % 2 == 0 {
+                hm.insert_borrowed_owned_key(i.to_string(), val);
+            } else {
+                hm.insert_owned(i.to_string(), val.clone());
+            }
+        }
+
+        let serial: usize = hm.values().map(|v| v.len()).sum();
+        let parallel: usize = hm.par_values().map(|v| v.len()).sum();
+        let parallel_iter: usize = hm.par_iter().map(|(_, v)| v.len()).sum();
+
+        assert_eq!(serial, parallel);
+        assert_eq!(serial, parallel_iter);
+        assert_eq!(hm.entry_form("0"), Some(Form::Borrowed));
+        assert_eq!(hm.entry_form("1"), Some(Form::Owned));
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2019 Octavian Oncescu
+
+use ::serde::de::{Deserialize, Deserializer, MapAccess, Visitor};
+use ::serde::ser::{Serialize, SerializeMap, Serializer};
+use alloc::borrow::ToOwned;
+use core::fmt;
+use core::hash::{BuildHasher, Hash};
+use core::marker::PhantomData;
+use crate::CowHashMap;
+
+impl<'a, K, V, S> Serialize for CowHashMap<'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned + Serialize,
+          V: ToOwned + ?Sized + Serialize,
+          S: BuildHasher,
+{
+    /// Serializes the map as a regular map of its keys and values.
+    /// Borrowed and owned entries are serialized identically.
+    fn serialize<T: Serializer>(&self, serializer: T) -> Result<T::Ok, T::Error> {
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+
+        for (key, val) in self.iter() {
+            map.serialize_entry(key, val)?;
+        }
+
+        map.end()
+    }
+}
+
+impl<'de, 'a, K, V, S> Deserialize<'de> for CowHashMap<'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher + Default,
+          <K as ToOwned>::Owned: Deserialize<'de>,
+          <V as ToOwned>::Owned: Deserialize<'de>,
+{
+    /// Deserializes a map. All of the resulting entries are in owned form.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_map(CowHashMapVisitor { marker: PhantomData })
+    }
+}
+
+struct CowHashMapVisitor<'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+{
+    marker: PhantomData<CowHashMap<'a, K, V, S>>
+}
+
+impl<'de, 'a, K, V, S> Visitor<'de> for CowHashMapVisitor<'a, K, V, S>
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          V: ToOwned + ?Sized,
+          S: BuildHasher + Default,
+          <K as ToOwned>::Owned: Deserialize<'de>,
+          <V as ToOwned>::Owned: Deserialize<'de>,
+{
+    type Value = CowHashMap<'a, K, V, S>;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut map = CowHashMap::with_capacity_and_hasher(access.size_hint().unwrap_or(0), S::default());
+
+        while let Some((key, val)) = access.next_entry()? {
+            map.insert_owned(key, val);
+        }
+
+        Ok(map)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Form, CowHashMap};
+
+    #[test]
+    fn json_round_trip() {
+        let borrowed = "value1".to_owned();
+        let mut hm: CowHashMap<str, String> = CowHashMap::new();
+        hm.insert_borrowed("key1", &borrowed);
+        hm.insert_owned("key2".to_owned(), "value2".to_owned());
+
+        let json = serde_json::to_string(&hm).unwrap();
+        let deserialized: CowHashMap<str, String> = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(deserialized, hm);
+        assert_eq!(deserialized.entry_form("key1"), Some(Form::Owned));
+        assert_eq!(deserialized.entry_form("key2"), Some(Form::Owned));
+    }
+
+    #[test]
+    fn borrowed_and_owned_serialize_identically() {
+        let value = "value".to_owned();
+
+        let mut borrowed: CowHashMap<str, String> = CowHashMap::new();
+        borrowed.insert_borrowed("key", &value);
+
+        let mut owned: CowHashMap<str, String> = CowHashMap::new();
+        owned.insert_owned("key".to_owned(), value.clone());
+
+        assert_eq!(serde_json::to_string(&borrowed).unwrap(), r#"{"key":"value"}"#);
+        assert_eq!(serde_json::to_string(&owned).unwrap(), r#"{"key":"value"}"#);
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright 2019 Octavian Oncescu
+
+use alloc::borrow::ToOwned;
+use alloc::sync::Arc;
+use core::hash::{BuildHasher, Hash};
+use crate::CowHashMap;
+
+/// Maps with `Arc` values.
+/// 
+/// An `Arc<T>` is its own owned form, so materializing a borrowed
+/// entry with `make_owned`, `get_mut` or any other function which
+/// clones values only bumps the reference count of the `Arc` instead
+/// of copying the shared data.
+impl<'a, K, T, S> CowHashMap<'a, K, Arc<T>, S> 
+    where K: Hash + ?Sized + PartialEq + Eq + ToOwned,
+          T: ?Sized,
+          S: BuildHasher,
+{
+    /// Inserts a key and a shared value into the map.
+    /// 
+    /// The entry is stored in owned form, holding a reference to the
+    /// shared data. If the map did have this key present, the old
+    /// value is returned.
+    /// 
+    /// ## Example
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use hashcow::{Form, CowHashMap};
+    /// 
+    /// let shared: Arc<[u8]> = Arc::from(vec![1, 2, 3]);
+    /// let mut hm: CowHashMap<str, Arc<[u8]>> = CowHashMap::new();
+    /// hm.insert_shared("key".to_owned(), shared.clone());
+    /// 
+    /// assert!(Arc::ptr_eq(hm.get(&"key").unwrap(), &shared));
+    /// assert_eq!(hm.entry_form(&"key").unwrap(), Form::Owned);
+    /// ```
+    #[inline]
+    pub fn insert_shared(&mut self, key: <K as ToOwned>::Owned, value: Arc<T>) -> Option<Arc<T>> {
+        self.insert_owned(key, value)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use crate::{Form, CowHashMap};
+    use std::sync::Arc;
+
+    #[test]
+    fn make_owned_shares_arc() {
+        let shared: Arc<[u8]> = Arc::from(vec![1, 2, 3]);
+        let mut hm: CowHashMap<str, Arc<[u8]>> = CowHashMap::new();
+        hm.insert_borrowed("key", &shared);
+
+        assert_eq!(Arc::strong_count(&shared), 1);
+
+        hm.make_owned("key");
+
+        assert_eq!(hm.entry_form("key"), Some(Form::Owned));
+        assert_eq!(Arc::strong_count(&shared), 2);
+        assert!(Arc::ptr_eq(hm.get("key").unwrap(), &shared));
+    }
+
+    #[test]
+    fn clones_and_shared_inserts_bump_refcount() {
+        let shared: Arc<[u8]> = Arc::from(vec![1, 2, 3]);
+        let mut hm: CowHashMap<str, Arc<[u8]>> = CowHashMap::new();
+
+        assert!(hm.insert_shared("key".to_owned(), shared.clone()).is_none());
+
+        let hm_clone = hm.clone();
+        let borrowed = hm.borrow_fields();
+
+        assert_eq!(Arc::strong_count(&shared), 3);
+        assert!(Arc::ptr_eq(hm_clone.get("key").unwrap(), &shared));
+        assert!(Arc::ptr_eq(borrowed.get("key").unwrap(), &shared));
+    }
+}
@@ -0,0 +1,47 @@
+// Copyright 2019 Octavian Oncescu
+
+use alloc::borrow::ToOwned;
+use alloc::vec::Vec;
+use core::fmt;
+use crate::Form;
+
+/// A snapshot of the form of every entry in a `CowHashMap`.
+///
+/// This is constructed from the `form_snapshot` method on `CowHashMap`
+/// and can be compared against the map later on with `diff_forms`.
+/// Only the forms are captured, not the values. The snapshot cannot be
+/// used to restore borrowed entries, since re-borrowing a value requires
+/// a reference which the map cannot produce by itself; use `make_borrowed`
+/// with the original reference for that.
+pub struct FormSnapshot<K>
+    where K: ToOwned + ?Sized,
+{
+    pub(crate) forms: Vec<(<K as ToOwned>::Owned, Form)>,
+}
+
+impl<K> FormSnapshot<K>
+    where K: ToOwned + ?Sized,
+{
+    /// Returns the number of entries captured in the snapshot.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.forms.len()
+    }
+
+    /// Returns `true` if the snapshot was taken of an empty map.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.forms.is_empty()
+    }
+}
+
+impl<K> fmt::Debug for FormSnapshot<K>
+    where K: ToOwned + ?Sized,
+          <K as ToOwned>::Owned: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FormSnapshot")
+            .field("forms", &self.forms)
+            .finish()
+    }
+}